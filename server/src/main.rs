@@ -0,0 +1,284 @@
+//! Keeps the games of online rooms and relays them to the players.
+//!
+//! Every WebSocket gets a thread that reads the player's messages and
+//! writes whatever other threads queued for it. The rooms themselves live
+//! in one `Lobby` behind a mutex; see `memory_game::online::room`.
+//!
+//! Usage: `memory_server [address]`, by default `127.0.0.1:9001`.
+
+use std::collections::HashMap;
+use std::env;
+use std::io;
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use memory_game::engine::Phase;
+use memory_game::online::protocol::{ClientMessage, OnlineError, ServerMessage, Ticket, DEFAULT_PORT};
+use memory_game::online::room::{Lobby, RoomError};
+use memory_game::timing;
+use tungstenite::{Error, Message, WebSocket};
+
+/// How much longer than the slowest browser a turned set stays up before
+/// the server settles it, to cover the trip over the network.
+const RESOLVE_MARGIN: Duration = Duration::from_millis(200);
+
+/// How often a connection stops waiting for its player to check for
+/// messages from the others.
+const POLL: Duration = Duration::from_millis(50);
+
+/// How long a browser may take to upgrade its connection to a WebSocket.
+const HANDSHAKE: Duration = Duration::from_secs(10);
+
+#[derive(Default)]
+struct Server {
+    lobby: Lobby,
+    /// Where to send messages for each seat, and which connection that is,
+    /// so a stale connection closing doesn't unseat a newer one.
+    clients: HashMap<(String, usize), (u64, Sender<ServerMessage>)>,
+    connections: u64,
+}
+
+struct Client {
+    id: u64,
+    outbox: Sender<ServerMessage>,
+    seat: Option<(String, usize)>,
+}
+
+type Shared = Arc<Mutex<Server>>;
+
+fn main() {
+    let addr = env::args().nth(1).unwrap_or_else(|| format!("127.0.0.1:{}", DEFAULT_PORT));
+    let listener = match TcpListener::bind(&addr) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("could not listen on {}: {}", addr, e);
+            std::process::exit(1);
+        }
+    };
+    println!("memory server listening on ws://{}", addr);
+
+    let server: Shared = Arc::default();
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let server = server.clone();
+                thread::spawn(move || serve(server, stream));
+            }
+            Err(e) => eprintln!("connection failed: {}", e),
+        }
+    }
+}
+
+fn serve(server: Shared, stream: TcpStream) {
+    if stream.set_read_timeout(Some(HANDSHAKE)).is_err() {
+        return;
+    }
+    let mut socket = match tungstenite::accept(stream) {
+        Ok(socket) => socket,
+        Err(e) => {
+            eprintln!("handshake failed: {}", e);
+            return;
+        }
+    };
+    // Only polled once upgraded: a short timeout fails slow handshakes.
+    if socket.get_ref().set_read_timeout(Some(POLL)).is_err() {
+        return;
+    }
+
+    let (outbox, inbox) = mpsc::channel();
+    let mut client = Client {
+        id: {
+            let mut server = server.lock().unwrap();
+            server.connections += 1;
+            server.connections
+        },
+        outbox,
+        seat: None,
+    };
+
+    loop {
+        match socket.read() {
+            Ok(Message::Text(text)) => match serde_json::from_str(&text) {
+                Ok(ClientMessage::Leave) => {
+                    leave(&server, &mut client, true);
+                    break;
+                }
+                Ok(message) => handle(&server, &mut client, message),
+                Err(_) => send(&client, error(OnlineError::Unreadable)),
+            },
+            Ok(Message::Close(_)) => break,
+            Ok(_) => {}
+            Err(Error::Io(e)) if e.kind() == io::ErrorKind::WouldBlock || e.kind() == io::ErrorKind::TimedOut => {}
+            Err(_) => break,
+        }
+        if !flush(&mut socket, &inbox) {
+            break;
+        }
+    }
+
+    leave(&server, &mut client, false);
+}
+
+/// Writes everything queued for this connection. After a `Rejected` the
+/// socket is closed; returns whether it is still open.
+fn flush(socket: &mut WebSocket<TcpStream>, inbox: &Receiver<ServerMessage>) -> bool {
+    while let Ok(message) = inbox.try_recv() {
+        let rejected = matches!(message, ServerMessage::Rejected { .. });
+        let text = match serde_json::to_string(&message) {
+            Ok(text) => text,
+            Err(_) => continue,
+        };
+        if socket.send(Message::Text(text)).is_err() {
+            return false;
+        }
+        if rejected {
+            let _ = socket.close(None);
+            let _ = socket.flush();
+            return false;
+        }
+    }
+    true
+}
+
+fn handle(server: &Shared, client: &mut Client, message: ClientMessage) {
+    match (message, client.seat.clone()) {
+        (ClientMessage::Create { .. }, Some(_))
+        | (ClientMessage::Join { .. }, Some(_))
+        | (ClientMessage::Rejoin(_), Some(_)) => send(client, error(OnlineError::AlreadySeated)),
+        (ClientMessage::Create { config, profile }, None) => {
+            let ticket = server.lock().unwrap().lobby.create(config, profile, &mut rand::thread_rng());
+            enter(server, client, ticket);
+        }
+        (ClientMessage::Join { code, profile }, None) => {
+            let ticket = server.lock().unwrap().lobby.join(&code, profile, &mut rand::thread_rng());
+            enter(server, client, ticket);
+        }
+        (ClientMessage::Rejoin(ticket), None) => {
+            let result = server.lock().unwrap().lobby.rejoin(&ticket).map(|_| ticket);
+            enter(server, client, result);
+        }
+        (ClientMessage::Flip { position }, Some((code, seat))) => {
+            if let Err(e) = flip(server, &code, seat, position) {
+                send(client, error(e));
+            }
+        }
+        (ClientMessage::Flip { .. }, None) => send(client, error(RoomError::NotSeated)),
+        (ClientMessage::Leave, _) => {}
+    }
+}
+
+/// Seats the client with `ticket` and shows the room to everyone in it.
+fn enter(server: &Shared, client: &mut Client, ticket: Result<Ticket, RoomError>) {
+    let ticket = match ticket {
+        Ok(ticket) => ticket,
+        Err(e) => {
+            send(client, ServerMessage::Rejected { error: e.into() });
+            return;
+        }
+    };
+    let key = (ticket.code.clone(), ticket.seat);
+    client.seat = Some(key.clone());
+    send(client, ServerMessage::Joined(ticket.clone()));
+
+    let mut server = server.lock().unwrap();
+    server.clients.insert(key, (client.id, client.outbox.clone()));
+    server.show_room(&ticket.code);
+}
+
+fn flip(shared: &Shared, code: &str, seat: usize, position: usize) -> Result<(), RoomError> {
+    let mut server = shared.lock().unwrap();
+    let room = server.lobby.room(code)?;
+    let events = room.flip(seat, position, now())?;
+    if events.is_empty() {
+        return Ok(());
+    }
+
+    let resolving = room.phase() == Phase::Resolving;
+    let update = room.update(events);
+    server.broadcast(code, &update);
+    if resolving {
+        schedule_resolve(shared.clone(), code.to_string());
+    }
+    Ok(())
+}
+
+/// Settles the turned set once everyone has had a look at it.
+fn schedule_resolve(shared: Shared, code: String) {
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(timing::slowest_settle().into()) + RESOLVE_MARGIN);
+        let mut server = shared.lock().unwrap();
+        let update = match server.lobby.room(&code) {
+            Ok(room) => {
+                let events = room.resolve(now());
+                if events.is_empty() {
+                    return;
+                }
+                room.update(events)
+            }
+            Err(_) => return,
+        };
+        server.broadcast(&code, &update);
+    });
+}
+
+/// Frees or keeps the client's seat, see `Room::leave`, and closes the
+/// room once nobody is left.
+fn leave(server: &Shared, client: &mut Client, on_purpose: bool) {
+    let (code, seat) = match client.seat.take() {
+        Some(key) => key,
+        None => return,
+    };
+    let mut server = server.lock().unwrap();
+    let current = server.clients.get(&(code.clone(), seat)).is_some_and(|(id, _)| *id == client.id);
+    if !current {
+        return;
+    }
+
+    server.clients.remove(&(code.clone(), seat));
+    if let Ok(room) = server.lobby.room(&code) {
+        if on_purpose {
+            // The room message below carries the game with the turn passed.
+            room.leave(seat);
+        } else {
+            room.disconnect(seat);
+        }
+    }
+    if server.lobby.close_if_deserted(&code) {
+        server.clients.retain(|(room, _), _| *room != code);
+    } else {
+        server.show_room(&code);
+    }
+}
+
+impl Server {
+    fn broadcast(&self, code: &str, message: &ServerMessage) {
+        for ((room, _), (_, outbox)) in &self.clients {
+            if room == code {
+                let _ = outbox.send(message.clone());
+            }
+        }
+    }
+
+    fn show_room(&mut self, code: &str) {
+        if let Ok(room) = self.lobby.room(code) {
+            let message = room.message();
+            self.broadcast(code, &message);
+        }
+    }
+}
+
+/// Scoring only looks at the time between moves, so any clock will do.
+fn now() -> f64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0.0, |d| d.as_millis() as f64)
+}
+
+fn send(client: &Client, message: ServerMessage) {
+    let _ = client.outbox.send(message);
+}
+
+fn error<T: Into<OnlineError>>(error: T) -> ServerMessage {
+    ServerMessage::Error { error: error.into() }
+}
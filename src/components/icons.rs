@@ -0,0 +1,118 @@
+use dominator::{svg, Dom};
+
+/// A single glyph drawn on a 24x24 grid with `currentColor`.
+#[derive(Debug)]
+pub struct Icon {
+    pub name: &'static str,
+    pub path: &'static str,
+}
+
+/// A named collection of icons. Card values index into `icons`, so a set
+/// needs one icon per pair on the biggest board it should support.
+#[derive(Debug)]
+pub struct IconSet {
+    pub name: &'static str,
+    pub icons: &'static [Icon],
+}
+
+pub const SHAPES: IconSet = IconSet {
+    name: "Shapes",
+    icons: &[
+        Icon { name: "circle", path: "M12 3a9 9 0 1 0 0 18a9 9 0 1 0 0-18z" },
+        Icon { name: "square", path: "M4 4h16v16H4z" },
+        Icon { name: "triangle", path: "M12 3L22 20H2z" },
+        Icon { name: "diamond", path: "M12 2L22 12L12 22L2 12z" },
+        Icon { name: "star", path: "M12 2l2.9 6.9L22 9.6l-5.5 4.8L18.2 22L12 18.3L5.8 22l1.7-7.6L2 9.6l7.1-.7z" },
+        Icon { name: "heart", path: "M12 21l-1.5-1.4C5.4 15 2 12 2 8.3C2 5.3 4.3 3 7.3 3c1.7 0 3.4.8 4.7 2.1C13.3 3.8 15 3 16.7 3C19.7 3 22 5.3 22 8.3c0 3.7-3.4 6.7-8.5 11.3z" },
+        Icon { name: "plus", path: "M9 2h6v7h7v6h-7v7H9v-7H2V9h7z" },
+        Icon { name: "cross", path: "M5 2l7 7l7-7l3 3l-7 7l7 7l-3 3l-7-7l-7 7l-3-3l7-7l-7-7z" },
+        Icon { name: "hexagon", path: "M12 2l8.7 5v10L12 22l-8.7-5V7z" },
+        Icon { name: "moon", path: "M20 15.5A9 9 0 0 1 8.5 4a9 9 0 1 0 11.5 11.5z" },
+        Icon { name: "bolt", path: "M13 2L4 14h7l-1 8l9-12h-7z" },
+        Icon { name: "drop", path: "M12 2C12 2 5 10 5 15a7 7 0 0 0 14 0C19 10 12 2 12 2z" },
+        Icon { name: "ring", path: "M12 2a10 10 0 1 0 0 20a10 10 0 1 0 0-20zM12 17a5 5 0 1 1 0-10a5 5 0 1 1 0 10z" },
+        Icon { name: "arrow", path: "M12 2l9 9h-6v11H9V11H3z" },
+        Icon { name: "flag", path: "M5 2h2v20H5zM8 3h12l-3 4.5l3 4.5H8z" },
+        Icon { name: "leaf", path: "M4 20C4 10 10 4 21 3C20 14 14 20 4 20z" },
+        Icon { name: "cloud", path: "M7 19a5 5 0 0 1-.6-10A6 6 0 0 1 18 8a4.5 4.5 0 0 1 0 11z" },
+        Icon { name: "pentagon", path: "M12 2l10 7.3l-3.8 11.7H5.8L2 9.3z" },
+        Icon { name: "sun", path: "M12 7a5 5 0 1 0 0 10a5 5 0 1 0 0-10zM11 1h2v4h-2zM11 19h2v4h-2zM1 11h4v2H1zM19 11h4v2h-4z" },
+        Icon { name: "octagon", path: "M8 2h8l6 6v8l-6 6H8l-6-6V8z" },
+        Icon { name: "bars", path: "M3 4h18v4H3zM3 10h18v4H3zM3 16h18v4H3z" },
+        Icon { name: "frame", path: "M3 3h18v18H3zM8 8v8h8V8z" },
+        Icon { name: "dome", path: "M2 14a10 10 0 0 1 20 0z" },
+        Icon { name: "hourglass", path: "M5 2h14l-7 10l7 10H5l7-10z" },
+        Icon { name: "bowtie", path: "M2 5l10 7l-10 7zM22 5l-10 7l10 7z" },
+        Icon { name: "chevron", path: "M2 8l10 8l10-8v6l-10 8l-10-8z" },
+        Icon { name: "house", path: "M12 2l10 9h-3v11H5V11H2z" },
+        Icon { name: "shield", path: "M12 2l9 4v6c0 5-4 9-9 10c-5-1-9-5-9-10V6z" },
+        Icon { name: "egg", path: "M12 2c4 0 7 7 7 12a7 7 0 0 1-14 0c0-5 3-12 7-12z" },
+        Icon { name: "oval", path: "M12 7c6 0 10 2.2 10 5s-4 5-10 5s-10-2.2-10-5s4-5 10-5z" },
+        Icon { name: "trapezoid", path: "M7 5h10l5 14H2z" },
+        Icon { name: "parallelogram", path: "M8 5h14l-6 14H2z" },
+        Icon { name: "kite", path: "M12 2l7 8l-7 12l-7-12z" },
+        Icon { name: "dots", path: "M7 3a4 4 0 1 0 0 8a4 4 0 1 0 0-8zM17 3a4 4 0 1 0 0 8a4 4 0 1 0 0-8zM7 13a4 4 0 1 0 0 8a4 4 0 1 0 0-8zM17 13a4 4 0 1 0 0 8a4 4 0 1 0 0-8z" },
+        Icon { name: "checker", path: "M3 3h9v9H3zM12 12h9v9h-9z" },
+        Icon { name: "steps", path: "M2 22v-6h6v-6h6V4h8v18z" },
+        Icon { name: "tee", path: "M2 3h20v6h-7v13H9V9H2z" },
+        Icon { name: "corner", path: "M4 2h6v14h10v6H4z" },
+        Icon { name: "zigzag", path: "M2 9l5-5l5 5l5-5l5 5v6l-5-5l-5 5l-5-5l-5 5z" },
+        Icon { name: "bell", path: "M12 2a7 7 0 0 1 7 7v7l3 3H2l3-3V9a7 7 0 0 1 7-7zM9 20h6a3 3 0 0 1-6 0z" },
+        Icon { name: "target", path: "M12 1a11 11 0 1 0 0 22a11 11 0 1 0 0-22zM12 5a7 7 0 1 1 0 14a7 7 0 1 1 0-14zM12 9a3 3 0 1 0 0 6a3 3 0 1 0 0-6z" },
+        Icon { name: "window", path: "M3 3h18v18H3zM5 5v6h6V5zM13 5v6h6V5zM5 13v6h6v-6zM13 13v6h6v-6z" },
+        Icon { name: "arch", path: "M3 22V11a9 9 0 0 1 18 0v11h-5V11a4 4 0 0 0-8 0v11z" },
+        Icon { name: "wave", path: "M2 10c3-4 6-4 10 0s7 4 10 0v8c-3 4-6 4-10 0s-7-4-10 0z" },
+        Icon { name: "crown", path: "M2 6l5 5l5-7l5 7l5-5l-2 14H4z" },
+        Icon { name: "tag", path: "M2 12l8-9h12v18H10zM16 10a2 2 0 1 0 0 4a2 2 0 1 0 0-4z" },
+        Icon { name: "pill", path: "M8 6h8a6 6 0 0 1 0 12H8a6 6 0 0 1 0-12z" },
+        Icon { name: "eye", path: "M2 12c5-8 15-8 20 0c-5 8-15 8-20 0zM12 9a3 3 0 1 0 0 6a3 3 0 1 0 0-6z" },
+        Icon { name: "lock", path: "M6 10V7a6 6 0 0 1 12 0v3h2v12H4V10zM9 10h6V7a3 3 0 0 0-6 0z" },
+        Icon { name: "hexagram", path: "M12 2l3 5h6l-3 5l3 5h-6l-3 5l-3-5H3l3-5l-3-5h6z" },
+        Icon { name: "gem", path: "M12 2l10 10l-10 10L2 12zM12 7l-5 5l5 5l5-5z" },
+    ],
+};
+
+/// Every registered set, the default first. Add new sets here.
+pub const SETS: &[IconSet] = &[SHAPES];
+
+impl IconSet {
+    pub fn icon(&self, value: usize) -> &'static Icon {
+        &self.icons[value % self.icons.len()]
+    }
+
+    pub fn render(&self, value: usize) -> Dom {
+        let icon = self.icon(value);
+        svg!("svg", {
+            .attr("viewBox", "0 0 24 24")
+            .attr("aria-label", icon.name)
+            .attr("role", "img")
+            .children(&mut [
+                svg!("path", {
+                    .attr("d", icon.path)
+                    .attr("fill", "currentColor")
+                    .attr("fill-rule", "evenodd")
+                })
+            ])
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::MAX_SIDE;
+    use std::collections::HashSet;
+
+    #[test]
+    fn sets_cover_the_largest_board_without_repeats() {
+        for set in SETS {
+            let pairs = MAX_SIDE * MAX_SIDE / 2;
+            assert!(set.icons.len() >= pairs, "{} is too small", set.name);
+
+            let names: HashSet<_> = set.icons.iter().map(|i| i.name).collect();
+            let paths: HashSet<_> = set.icons.iter().map(|i| i.path).collect();
+            assert_eq!(names.len(), set.icons.len());
+            assert_eq!(paths.len(), set.icons.len());
+        }
+    }
+}
@@ -1,15 +1,32 @@
-use futures::future::ready;
-use dominator::{clone, events, html, Dom};
+use dominator::{clone, events, html, with_node, Dom};
 use futures_signals::{
-    signal::SignalExt,
-    signal_vec::{MutableVec, SignalVecExt},
+    map_ref,
+    signal::{Mutable, SignalExt},
 };
+use std::iter;
 use std::sync::Arc;
 
-use crate::{App, Config, GameStates, GameTheme};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{File, HtmlInputElement};
+
+use crate::engine::bot::Difficulty;
+use crate::engine::scoring::Scoring;
+use crate::engine::turns::{TurnRule, SHOT_CLOCKS};
+use crate::engine::{SizeError, HINT_TIME_PENALTY, MAX_SIDE, MIN_SIDE, SET_SIZES};
+use crate::i18n::Locale;
+use crate::online::client;
+use crate::profiles::{self, Color, MAX_NAME};
+use crate::{daily, files};
+use crate::replay_file::{ImportError, ReplayFile};
+use crate::sound::Sound;
+use crate::timing::Speed;
+use crate::{capitalize, format_time, random_seed, render_badge, today, App, Config, GameStates, GameTheme};
 
 pub struct InitialScreen;
 
+/// Hints per game offered on the setup screen.
+const HINT_CHOICES: [usize; 4] = [0, 1, 3, 5];
+
 impl InitialScreen {
     pub fn render(&self, app: Arc<App>) -> Dom {
         let base = "initial";
@@ -19,7 +36,7 @@ impl InitialScreen {
             .children(&mut [
                       html!("h1", {
                         .class(format!("{}_title", base))
-                        .text("memory")
+                        .text(&app.t("app.title"))
                       }),
                     self.render_config(app),
             ])
@@ -31,8 +48,12 @@ impl InitialScreen {
         let number_selected = app.config.signal_ref(|val| val.theme == GameTheme::Numbers);
         let icons_selected = app.config.signal_ref(|val| val.theme == GameTheme::Icons);
 
-        let size4_selected = app.config.signal_ref(|val| val.size == 16);
-        let size6_selected = app.config.signal_ref(|val| val.size == 36);
+        let size4_selected = app.config.signal_ref(|val| val.rows == 4 && val.columns == 4);
+        let size6_selected = app.config.signal_ref(|val| val.rows == 6 && val.columns == 6);
+        let size_form = Arc::new(SizeForm::new(&app.config.lock_ref()));
+        let replay_error = Mutable::new(None);
+        let server_url = Mutable::new(client::default_url());
+        let room_code = Mutable::new(String::new());
 
         html! {"div",{
             .class(base)
@@ -42,7 +63,31 @@ impl InitialScreen {
                           .children(&mut [
                             html!{"h3", {
                                 .class(format!("{}_label", base))
-                                .text("Select Theme")
+                                .text_signal(app.t_signal("setup.language"))
+                            }},
+                            html!{"div", {
+                                .class(format!("{}_options", base))
+                                .children(iter::once(None).chain(Locale::ALL.iter().copied().map(Some)).map(|choice| html!{"button", {
+                                    .class("btn")
+                                    .class_signal("selected", app.settings.signal_ref(move |s| s.locale == choice))
+                                    .class("bg_gray_100")
+                                    .apply_if(choice.is_some(), |dom| dom.attr("lang", choice.map_or("", |l| l.code())))
+                                    .text_signal(app.localized(move |locale| choice.map_or_else(|| locale.t("language.auto"), |l| l.name().to_string())))
+                                    .event(clone!(app => move |_: events::Click| {
+                                        App::change_settings(app.clone(), |settings| settings.locale = choice);
+                                    }))
+                                }}))
+                            }},
+
+                          ])
+                      }),
+
+                      html!("div", {
+                          .class("row")
+                          .children(&mut [
+                            html!{"h3", {
+                                .class(format!("{}_label", base))
+                                .text_signal(app.t_signal("setup.theme"))
                             }},
                             html!{"div", {
                                 .class(format!("{}_options", base))
@@ -51,18 +96,18 @@ impl InitialScreen {
                                         .class("btn")
                                         .class_signal("selected", number_selected)
                                         .class("bg_gray_100")
-                                        .text("Numbers")
+                                        .text_signal(app.t_signal("theme.numbers"))
                                         .event(clone!(app => move |_: events::Click| {
-                                            app.config.replace_with(|cfg| Config{ theme: GameTheme::Numbers, ..*cfg});
+                                            App::change_config(app.clone(), |cfg| Config{ theme: GameTheme::Numbers, ..*cfg});
                                         }))
                                     }},
                                     html!{"button", {
                                         .class("btn")
                                         .class_signal("selected", icons_selected)
                                         .class("bg_gray_100")
-                                        .text("Icons")
+                                        .text_signal(app.t_signal("theme.icons"))
                                         .event(clone!(app => move |_: events::Click| {
-                                            app.config.replace_with(|cfg| Config{ theme: GameTheme::Icons, ..*cfg});
+                                            App::change_config(app.clone(), |cfg| Config{ theme: GameTheme::Icons, ..*cfg});
                                         }))
                                     }}
                                 ])
@@ -71,21 +116,98 @@ impl InitialScreen {
                           ])
                       }),
 
-                    html!("div", {
+                      html!("div", {
+                          .class("row")
+                          .children(&mut [
+                            html!{"h3", {
+                                .class(format!("{}_label", base))
+                                .text_signal(app.t_signal("setup.animations"))
+                            }},
+                            html!{"div", {
+                                .class(format!("{}_options", base))
+                                .children(Speed::ALL.iter().map(|&speed| html!{"button", {
+                                    .class("btn")
+                                    .class_signal("selected", app.settings.signal_ref(move |s| s.speed == speed))
+                                    .class("bg_gray_100")
+                                    .text_signal(app.localized(move |locale| locale.t(&format!("speed.{}", speed.key()))))
+                                    .event(clone!(app => move |_: events::Click| {
+                                        App::change_settings(app.clone(), |settings| settings.speed = speed);
+                                    }))
+                                }}))
+                            }},
+                            html!{"p", {
+                                .class(format!("{}_record", base))
+                                .visible_signal(map_ref! {
+                                    let reduced = app.reduced_motion.signal(),
+                                    let speed = app.settings.signal_ref(|s| s.speed) =>
+                                    *reduced && *speed != Speed::Instant
+                                })
+                                .text_signal(app.t_signal("setup.reduced_motion"))
+                            }},
+
+                          ])
+                      }),
+
+                      html!("div", {
                           .class("row")
                           .children(&mut [
                             html!{"h3", {
                                 .class(format!("{}_label", base))
-                                .text("Number of Players")
+                                .text_signal(app.t_signal("setup.sound"))
                             }},
                             html!{"div", {
                                 .class(format!("{}_options", base))
-                                .future(
-                                            app.config.signal_cloned().for_each(clone!( app => move |change| {
-                                                App::add_players(app.clone());
-                                               ready(())
+                                .children(&mut[
+                                    html!{"button", {
+                                        .class("btn")
+                                        .class("bg_gray_100")
+                                        .class_signal("selected", app.settings.signal_ref(|s| !s.muted))
+                                        .attr_signal("aria-pressed", app.settings.signal_ref(|s| if s.muted { "false" } else { "true" }))
+                                        .text_signal(map_ref! {
+                                            let locale = app.locale_signal(),
+                                            let muted = app.settings.signal_ref(|s| s.muted) =>
+                                            locale.t(if *muted { "sound.muted" } else { "sound.on" })
+                                        })
+                                        .event(clone!(app => move |_: events::Click| {
+                                            App::change_settings(app.clone(), |settings| settings.muted = !settings.muted);
+                                            app.play(Sound::Flip);
+                                        }))
+                                    }},
+                                    html!("input" => HtmlInputElement, {
+                                        .class(format!("{}_volume", base))
+                                        .attr("type", "range")
+                                        .attr("min", "0")
+                                        .attr("max", "100")
+                                        .attr("step", "5")
+                                        .attr_signal("aria-label", app.t_signal("sound.volume"))
+                                        .prop_signal("value", app.settings.signal_ref(|s| ((s.volume * 100.0).round() as u32).to_string()))
+                                        .prop_signal("disabled", app.settings.signal_ref(|s| s.muted))
+                                        .with_node!(input => {
+                                            .event(clone!(app => move |_: events::Input| {
+                                                if let Ok(percent) = input.value().parse::<f64>() {
+                                                    App::change_settings(app.clone(), |settings| settings.volume = (percent / 100.0).clamp(0.0, 1.0));
+                                                }
+                                            }))
+                                            .event(clone!(app => move |_: events::Change| {
+                                                app.play(Sound::Flip);
                                             }))
-                                        )
+                                        })
+                                    }),
+                                ])
+                            }},
+
+                          ])
+                      }),
+
+                    html!("div", {
+                          .class("row")
+                          .children(&mut [
+                            html!{"h3", {
+                                .class(format!("{}_label", base))
+                                .text_signal(app.t_signal("setup.players"))
+                            }},
+                            html!{"div", {
+                                .class(format!("{}_options", base))
                                 .children(&mut[
                                     html!{"button", {
                                         .class("btn")
@@ -93,7 +215,7 @@ impl InitialScreen {
                                         .class("bg_gray_100")
                                         .text("1")
                                         .event(clone!(app => move |_: events::Click| {
-                                            app.config.replace_with(|cfg| Config{ players: 1, ..*cfg});
+                                            App::change_config(app.clone(), |cfg| Config{ players: 1, ..*cfg});
                                         }))
                                     }},
                                     html!{"button", {
@@ -102,7 +224,7 @@ impl InitialScreen {
                                         .class("bg_gray_100")
                                         .text("2")
                                         .event(clone!(app => move |_: events::Click| {
-                                            app.config.replace_with(|cfg| Config{ players: 2, ..*cfg});
+                                            App::change_config(app.clone(), |cfg| Config{ players: 2, ..*cfg});
                                         }))
                                     }},
                                     html!{"button", {
@@ -111,7 +233,7 @@ impl InitialScreen {
                                         .class("bg_gray_100")
                                         .text("3")
                                         .event(clone!(app => move |_: events::Click| {
-                                            app.config.replace_with(|cfg| Config{ players: 3, ..*cfg});
+                                            App::change_config(app.clone(), |cfg| Config{ players: 3, ..*cfg});
                                         }))
                                     }},
                                     html!{"button", {
@@ -120,7 +242,7 @@ impl InitialScreen {
                                         .class("bg_gray_100")
                                         .text("4")
                                         .event(clone!(app => move |_: events::Click| {
-                                            app.config.replace_with(|cfg| Config{ players: 4, ..*cfg});
+                                            App::change_config(app.clone(), |cfg| Config{ players: 4, ..*cfg});
                                         }))
                                     }}
                                 ])
@@ -129,12 +251,80 @@ impl InitialScreen {
                           ])
                       }),
 
+                      html!("div", {
+                          .class("row")
+                          .children(&mut [
+                            html!{"h3", {
+                                .class(format!("{}_label", base))
+                                .text_signal(app.t_signal("setup.who"))
+                            }},
+                            html!{"div", {
+                                .class(format!("{}_players", base))
+                                .child_signal(app.config.signal_ref(|cfg| cfg.players).dedupe().map(clone!(app => move |players| {
+                                    Some(html!{"div", {
+                                        .children((0..players).map(|seat| Self::render_profile(app.clone(), seat)))
+                                    }})
+                                })))
+                            }},
+
+                          ])
+                      }),
+
                         html!("div", {
+                          .class("row")
+                          .visible_signal(app.config.signal_ref(|cfg| cfg.players > 1))
+                          .children(&mut [
+                            html!{"h3", {
+                                .class(format!("{}_label", base))
+                                .text_signal(app.t_signal("setup.turns"))
+                            }},
+                            html!{"div", {
+                                .class(format!("{}_options", base))
+                                .children(TurnRule::CHOICES.iter().map(|&turns| html!{"button", {
+                                    .class("btn")
+                                    .class_signal("selected", app.config.signal_ref(move |v| v.turns == turns))
+                                    .class("bg_gray_100")
+                                    .text_signal(app.localized(move |locale| locale.turn_rule(turns)))
+                                    .event(clone!(app => move |_: events::Click| {
+                                        App::change_config(app.clone(), |cfg| Config{ turns, ..cfg.clone() });
+                                    }))
+                                }}))
+                            }},
+                            html!{"p", {
+                                .class(format!("{}_record", base))
+                                .text_signal(map_ref! {
+                                    let locale = app.locale_signal(),
+                                    let turns = app.config.signal_ref(|cfg| cfg.turns) =>
+                                    locale.turn_rule_about(*turns)
+                                })
+                            }},
+                            html!{"div", {
+                                .class(format!("{}_options", base))
+                                .attr("role", "group")
+                                .attr_signal("aria-label", app.t_signal("setup.shot_clock"))
+                                .children(SHOT_CLOCKS.iter().map(|&shot_clock| html!{"button", {
+                                    .class("btn")
+                                    .class_signal("selected", app.config.signal_ref(move |v| v.shot_clock == shot_clock))
+                                    .class("bg_gray_100")
+                                    .text_signal(app.localized(move |locale| match shot_clock {
+                                        Some(seconds) => locale.t_with("shot_clock.seconds", &[("seconds", &seconds)]),
+                                        None => locale.t("shot_clock.none"),
+                                    }))
+                                    .event(clone!(app => move |_: events::Click| {
+                                        App::change_config(app.clone(), |cfg| Config{ shot_clock, ..cfg.clone() });
+                                    }))
+                                }}))
+                            }},
+
+                          ])
+                      }),
+
+                      html!("div", {
                           .class("row")
                           .children(&mut [
                             html!{"h3", {
                                 .class(format!("{}_label", base))
-                                .text("Grid Size")
+                                .text_signal(app.t_signal("setup.grid"))
                             }},
                             html!{"div", {
                                 .class(format!("{}_options", base))
@@ -144,8 +334,8 @@ impl InitialScreen {
                                         .class_signal("selected", size4_selected)
                                         .class("bg_gray_100")
                                         .text("4x4")
-                                        .event(clone!(app => move |_: events::Click| {
-                                            App::change_size(app.clone(), 16);
+                                        .event(clone!(app, size_form => move |_: events::Click| {
+                                            size_form.preset(app.clone(), 4, 4);
                                         }))
                                     }},
                                     html!{"button", {
@@ -153,8 +343,320 @@ impl InitialScreen {
                                         .class_signal("selected", size6_selected)
                                         .class("bg_gray_100")
                                         .text("6x6")
+                                        .event(clone!(app, size_form => move |_: events::Click| {
+                                            size_form.preset(app.clone(), 6, 6);
+                                        }))
+                                    }}
+                                ])
+                            }},
+                            html!{"div", {
+                                .class(format!("{}_options", base))
+                                .class(format!("{}_custom", base))
+                                .children(&mut[
+                                    size_form.render_input(app.clone(), "setup.rows", &size_form.rows),
+                                    html!{"span", {
+                                        .class(format!("{}_times", base))
+                                        .text("x")
+                                    }},
+                                    size_form.render_input(app.clone(), "setup.columns", &size_form.columns),
+                                ])
+                            }},
+                            html!{"p", {
+                                .class(format!("{}_error", base))
+                                .attr("role", "alert")
+                                .visible_signal(size_form.error.signal_ref(Option::is_some))
+                                .text_signal(map_ref! {
+                                    let locale = app.locale_signal(),
+                                    let error = size_form.error.signal_cloned() =>
+                                    error.as_ref().map(|e| e.describe(*locale)).unwrap_or_default()
+                                })
+                            }},
+
+                          ])
+                      }),
+
+                      html!("div", {
+                          .class("row")
+                          .children(&mut [
+                            html!{"h3", {
+                                .class(format!("{}_label", base))
+                                .text_signal(app.t_signal("setup.match"))
+                            }},
+                            html!{"div", {
+                                .class(format!("{}_options", base))
+                                .children(SET_SIZES.iter().map(|&set_size| {
+                                    html!{"button", {
+                                        .class("btn")
+                                        .class_signal("selected", size_form.set_size.signal_ref(move |s| *s == set_size))
+                                        .class("bg_gray_100")
+                                        .text_signal(app.localized(move |locale| capitalize(&locale.set_name(set_size, 2))))
+                                        .event(clone!(app, size_form => move |_: events::Click| {
+                                            size_form.set_matching(app.clone(), set_size);
+                                        }))
+                                    }}
+                                }))
+                            }},
+
+                          ])
+                      }),
+
+                      html!("div", {
+                          .class("row")
+                          .children(&mut [
+                            html!{"h3", {
+                                .class(format!("{}_label", base))
+                                .text_signal(app.t_signal("setup.hints"))
+                            }},
+                            html!{"div", {
+                                .class(format!("{}_options", base))
+                                .children(HINT_CHOICES.iter().map(|&hints| html!{"button", {
+                                    .class("btn")
+                                    .class_signal("selected", app.config.signal_ref(move |v| v.hints == hints))
+                                    .class("bg_gray_100")
+                                    .text(&hints.to_string())
+                                    .event(clone!(app => move |_: events::Click| {
+                                        App::change_config(app.clone(), |cfg| Config{ hints, ..cfg.clone() });
+                                    }))
+                                }}))
+                            }},
+                            html!{"p", {
+                                .class(format!("{}_record", base))
+                                .text_signal(app.localized(|locale| {
+                                    locale.t_with("setup.hints.about", &[("time", &format_time(HINT_TIME_PENALTY))])
+                                }))
+                            }},
+
+                          ])
+                      }),
+
+                      html!("div", {
+                          .class("row")
+                          .children(&mut [
+                            html!{"h3", {
+                                .class(format!("{}_label", base))
+                                .text_signal(app.t_signal("setup.scoring"))
+                            }},
+                            html!{"div", {
+                                .class(format!("{}_options", base))
+                                .children(Scoring::ALL.iter().map(|&scoring| html!{"button", {
+                                    .class("btn")
+                                    .class_signal("selected", app.config.signal_ref(move |v| v.scoring == scoring))
+                                    .class("bg_gray_100")
+                                    .text_signal(app.localized(move |locale| locale.t(&format!("scoring.{}", scoring.key()))))
+                                    .event(clone!(app => move |_: events::Click| {
+                                        App::change_config(app.clone(), |cfg| Config{ scoring, ..cfg.clone() });
+                                    }))
+                                }}))
+                            }},
+                            html!{"p", {
+                                .class(format!("{}_record", base))
+                                .text_signal(map_ref! {
+                                    let locale = app.locale_signal(),
+                                    let scoring = app.config.signal_ref(|cfg| cfg.scoring) =>
+                                    locale.t(&format!("scoring.{}.about", scoring.key()))
+                                })
+                            }},
+
+                          ])
+                      }),
+
+                      html!("div", {
+                          .class("row")
+                          .children(&mut [
+                            html!{"h3", {
+                                .class(format!("{}_label", base))
+                                .text_signal(app.t_signal("setup.seed"))
+                            }},
+                            html!{"div", {
+                                .class(format!("{}_options", base))
+                                .children(&mut[
+                                    html!("input" => HtmlInputElement, {
+                                        .class(format!("{}_input", base))
+                                        .attr("type", "text")
+                                        .attr("inputmode", "numeric")
+                                        .attr_signal("aria-label", app.t_signal("setup.seed.label"))
+                                        .prop_signal("value", app.config.signal_ref(|cfg| cfg.seed.to_string()))
+                                        .with_node!(input => {
+                                            .event(clone!(app => move |_: events::Input| {
+                                                if let Ok(seed) = input.value().trim().parse() {
+                                                    App::change_seed(app.clone(), seed);
+                                                }
+                                            }))
+                                        })
+                                    }),
+                                    html!{"button", {
+                                        .class("btn")
+                                        .class("bg_gray_100")
+                                        .text_signal(app.t_signal("setup.random"))
+                                        .event(clone!(app => move |_: events::Click| {
+                                            App::change_seed(app.clone(), random_seed());
+                                        }))
+                                    }}
+                                ])
+                            }},
+
+                          ])
+                      }),
+
+                      html!("div", {
+                          .class("row")
+                          .children(&mut [
+                            html!{"h3", {
+                                .class(format!("{}_label", base))
+                                .text_signal(app.t_signal("setup.daily"))
+                            }},
+                            html!{"div", {
+                                .class(format!("{}_options", base))
+                                .children(&mut[
+                                    html!{"p", {
+                                        .class(format!("{}_record", base))
+                                        .text_signal(map_ref! {
+                                            let locale = app.locale_signal(),
+                                            let daily = app.daily.signal_cloned() => {
+                                            let today = today();
+                                            let streak = locale.t_count("daily.streak", daily.streak(today) as usize, &[]);
+                                            match daily.get(today) {
+                                                Some(result) => {
+                                                    let moves = locale.t_count("moves", result.moves, &[]);
+                                                    let id = if result.abandoned { "daily.abandoned" } else { "daily.done" };
+                                                    locale.t_with(id, &[("moves", &moves), ("streak", &streak)])
+                                                }
+                                                None => locale.t_with(
+                                                    "daily.todo",
+                                                    &[("rows", &daily::ROWS), ("columns", &daily::COLUMNS), ("streak", &streak)],
+                                                ),
+                                            }
+                                        }})
+                                    }},
+                                    html!{"button", {
+                                        .class("btn")
+                                        .class("bg_gray_100")
+                                        .text_signal(app.t_signal("daily.play"))
+                                        .event(clone!(app => move |_: events::Click| {
+                                            App::start_daily(app.clone());
+                                        }))
+                                    }}
+                                ])
+                            }},
+
+                          ])
+                      }),
+
+                      html!("div", {
+                          .class("row")
+                          .children(&mut [
+                            html!{"h3", {
+                                .class(format!("{}_label", base))
+                                .text_signal(app.t_signal("setup.online"))
+                            }},
+                            html!{"div", {
+                                .class(format!("{}_options", base))
+                                .children(&mut[
+                                    html!("input" => HtmlInputElement, {
+                                        .class(format!("{}_input", base))
+                                        .attr("type", "url")
+                                        .attr_signal("aria-label", app.t_signal("online.server"))
+                                        .prop("value", &server_url.get_cloned())
+                                        .with_node!(input => {
+                                            .event(clone!(server_url => move |_: events::Input| {
+                                                server_url.set(input.value());
+                                            }))
+                                        })
+                                    }),
+                                    html!{"button", {
+                                        .class("btn")
+                                        .class("bg_gray_100")
+                                        .text_signal(app.t_signal("online.host"))
+                                        .prop_signal("disabled", map_ref! {
+                                            let players = app.config.signal_ref(|cfg| cfg.players),
+                                            let error = size_form.error.signal_ref(Option::is_some) =>
+                                            *players < 2 || *error
+                                        })
+                                        .event(clone!(app, server_url => move |_: events::Click| {
+                                            App::host_online(app.clone(), server_url.lock_ref().trim());
+                                        }))
+                                    }},
+                                ])
+                            }},
+                            html!{"div", {
+                                .class(format!("{}_options", base))
+                                .children(&mut[
+                                    html!("input" => HtmlInputElement, {
+                                        .class(format!("{}_input", base))
+                                        .attr("type", "text")
+                                        .attr("maxlength", "4")
+                                        .attr_signal("placeholder", app.t_signal("online.code"))
+                                        .attr_signal("aria-label", app.t_signal("online.code.label"))
+                                        .with_node!(input => {
+                                            .event(clone!(room_code => move |_: events::Input| {
+                                                room_code.set(input.value());
+                                            }))
+                                        })
+                                    }),
+                                    html!{"button", {
+                                        .class("btn")
+                                        .class("bg_gray_100")
+                                        .text_signal(app.t_signal("online.join"))
+                                        .prop_signal("disabled", room_code.signal_ref(|code| code.trim().is_empty()))
+                                        .event(clone!(app, server_url, room_code => move |_: events::Click| {
+                                            App::join_online(app.clone(), server_url.lock_ref().trim(), &room_code.lock_ref());
+                                        }))
+                                    }},
+                                ])
+                            }},
+                            html!{"p", {
+                                .class(format!("{}_record", base))
+                                .text_signal(map_ref! {
+                                    let locale = app.locale_signal(),
+                                    let label = app.online_label() =>
+                                    label.clone().unwrap_or_else(|| locale.t("online.about"))
+                                })
+                            }},
+                            html!{"p", {
+                                .class(format!("{}_error", base))
+                                .attr("role", "alert")
+                                .visible_signal(app.online_error.signal_ref(Option::is_some))
+                                .text_signal(map_ref! {
+                                    let locale = app.locale_signal(),
+                                    let error = app.online_error.signal_cloned() =>
+                                    error.as_ref().map(|e| locale.online_error(e)).unwrap_or_default()
+                                })
+                            }},
+
+                          ])
+                      }),
+
+                      html!("div", {
+                          .class("row")
+                          .children(&mut [
+                            html!{"h3", {
+                                .class(format!("{}_label", base))
+                                .text_signal(app.t_signal("setup.best"))
+                            }},
+                            html!{"div", {
+                                .class(format!("{}_options", base))
+                                .children(&mut[
+                                    html!{"p", {
+                                        .class(format!("{}_record", base))
+                                        .text_signal(map_ref! {
+                                            let locale = app.locale_signal(),
+                                            let cfg = app.config.signal_cloned(),
+                                            let records = app.records.signal_cloned() =>
+                                            match records.get(cfg) {
+                                                Some(best) => locale.t_with(
+                                                    "best.record",
+                                                    &[("time", &format_time(best.time)), ("moves", &locale.t_count("moves", best.moves, &[]))],
+                                                ),
+                                                None => locale.t("best.none"),
+                                            }
+                                        })
+                                    }},
+                                    html!{"button", {
+                                        .class("btn")
+                                        .class("bg_gray_100")
+                                        .text_signal(app.t_signal("best.reset"))
                                         .event(clone!(app => move |_: events::Click| {
-                                            App::change_size(app.clone(), 36);
+                                            App::reset_records(app.clone());
                                         }))
                                     }}
                                 ])
@@ -163,6 +665,47 @@ impl InitialScreen {
                           ])
                       }),
 
+                      html!("div", {
+                          .class("row")
+                          .children(&mut [
+                            html!{"h3", {
+                                .class(format!("{}_label", base))
+                                .text_signal(app.t_signal("setup.replay"))
+                            }},
+                            html!{"div", {
+                                .class(format!("{}_options", base))
+                                .children(&mut[
+                                    html!("input" => HtmlInputElement, {
+                                        .class(format!("{}_file", base))
+                                        .attr("type", "file")
+                                        .attr("accept", ".json,application/json")
+                                        .attr_signal("aria-label", app.t_signal("setup.replay.label"))
+                                        .with_node!(input => {
+                                            .event(clone!(app, replay_error => move |_: events::Change| {
+                                                let file = input.files().and_then(|files| files.get(0));
+                                                input.set_value("");
+                                                if let Some(file) = file {
+                                                    Self::import_replay(app.clone(), replay_error.clone(), file);
+                                                }
+                                            }))
+                                        })
+                                    }),
+                                ])
+                            }},
+                            html!{"p", {
+                                .class(format!("{}_error", base))
+                                .attr("role", "alert")
+                                .visible_signal(replay_error.signal_ref(Option::is_some))
+                                .text_signal(map_ref! {
+                                    let locale = app.locale_signal(),
+                                    let error = replay_error.signal_cloned() =>
+                                    error.as_ref().map(|e| locale.import_error(e)).unwrap_or_default()
+                                })
+                            }},
+
+                          ])
+                      }),
+
                       html!("div", {
                           .class("row")
                           .children(&mut [
@@ -173,10 +716,21 @@ impl InitialScreen {
                                         .class("btn")
                                         .class("big")
                                         .class("bg_orange")
-                                        .text("Start Game")
+                                        .text_signal(app.t_signal("setup.start"))
+                                        .prop_signal("disabled", size_form.error.signal_ref(Option::is_some))
                                         .event(clone!(app => move |_: events::Click| {
                                             App::go_play(app.clone());
                                         }))
+                                    }},
+                                    html!{"button", {
+                                        .class("btn")
+                                        .class("big")
+                                        .class("bg_gray_200")
+                                        .visible_signal(app.saved.signal_ref(Option::is_some))
+                                        .text_signal(app.t_signal("setup.resume"))
+                                        .event(clone!(app => move |_: events::Click| {
+                                            App::resume(app.clone());
+                                        }))
                                     }}
                                 ])
                             }},
@@ -188,3 +742,174 @@ impl InitialScreen {
         }}
     }
 }
+
+impl InitialScreen {
+    fn render_profile(app: Arc<App>, seat: usize) -> Dom {
+        let base = "config_player";
+        let profile = app.profiles.lock_ref()[seat].clone();
+        // Labels that name the seat, which reads differently in each language.
+        let about = |id: &'static str| {
+            app.localized(move |locale| {
+                let seat_name = locale.t_with("player.default", &[("number", &(seat + 1))]);
+                locale.t_with(id, &[("name", &seat_name)])
+            })
+        };
+
+        html!{"div", {
+            .class(base)
+            .children(&mut [
+                render_badge(
+                    app.profiles.signal_ref(clone!(app => move |p| profiles::initial(&app.seat_name(&p[seat], seat)))),
+                    app.profiles.signal_ref(move |p| p[seat].color.css()),
+                ),
+                html!("input" => HtmlInputElement, {
+                    .class("config_input")
+                    .attr("type", "text")
+                    .attr("maxlength", &MAX_NAME.to_string())
+                    .attr_signal("placeholder", app.localized(move |locale| {
+                        locale.t_with("player.default", &[("number", &(seat + 1))])
+                    }))
+                    .attr_signal("aria-label", about("profile.name_of"))
+                    .prop("value", &profile.name)
+                    .with_node!(input => {
+                        .event(clone!(app => move |_: events::Input| {
+                            let name = input.value();
+                            App::change_profile(app.clone(), seat, |p| p.name = name);
+                        }))
+                    })
+                }),
+                html!{"div", {
+                    .class(format!("{}__colors", base))
+                    .attr("role", "group")
+                    .attr_signal("aria-label", about("profile.color_of"))
+                    .children(Color::ALL.iter().map(|&color| html!{"button", {
+                        .class(format!("{}__color", base))
+                        .style("background-color", color.css())
+                        .attr_signal("aria-label", app.localized(move |locale| locale.t(&format!("color.{}", color.key()))))
+                        .class_signal("selected", app.profiles.signal_ref(move |p| p[seat].color == color))
+                        .attr_signal("aria-pressed", app.profiles.signal_ref(move |p| (p[seat].color == color).to_string()))
+                        .event(clone!(app => move |_: events::Click| {
+                            App::change_profile(app.clone(), seat, |p| p.color = color);
+                        }))
+                    }}))
+                }},
+                html!{"div", {
+                    .class(format!("{}__modes", base))
+                    .attr("role", "group")
+                    .attr_signal("aria-label", about("profile.who_plays"))
+                    .children(iter::once(None).chain(Difficulty::ALL.iter().copied().map(Some)).map(|mode| html!{"button", {
+                        .class("btn")
+                        .class("bg_gray_100")
+                        .class_signal("selected", app.config.signal_ref(move |cfg| cfg.computers[seat] == mode))
+                        .text_signal(app.localized(move |locale| {
+                            locale.t(&mode.map_or("profile.human".to_string(), |d| format!("difficulty.{}", d.key())))
+                        }))
+                        .event(clone!(app => move |_: events::Click| {
+                            App::change_config(app.clone(), |cfg| {
+                                let mut computers = cfg.computers;
+                                computers[seat] = mode;
+                                Config { computers, ..cfg.clone() }
+                            });
+                        }))
+                    }}))
+                }},
+            ])
+        }}
+    }
+
+    /// Reads a shared replay and opens it, or explains why it can't be.
+    fn import_replay(app: Arc<App>, error: Mutable<Option<ImportError>>, file: File) {
+        spawn_local(async move {
+            let result = match files::read_text(file).await {
+                Ok(text) => ReplayFile::from_json(&text),
+                Err(_) => Err(ImportError::Unreadable),
+            };
+            match result {
+                Ok(replay) => {
+                    error.set(None);
+                    App::load_replay(app, replay);
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        });
+    }
+}
+
+/// The custom rows x columns inputs. Only sizes that pass
+/// `engine::check_size` reach `Config`; anything else shows an error.
+struct SizeForm {
+    rows: Mutable<String>,
+    columns: Mutable<String>,
+    set_size: Mutable<usize>,
+    error: Mutable<Option<SizeProblem>>,
+}
+
+/// What's wrong with the typed size, kept untranslated so the message
+/// follows the language.
+#[derive(Clone, Debug)]
+enum SizeProblem {
+    NotNumbers,
+    Size(SizeError),
+}
+
+impl SizeProblem {
+    fn describe(&self, locale: Locale) -> String {
+        match self {
+            SizeProblem::NotNumbers => locale.t("size.not_numbers"),
+            SizeProblem::Size(error) => locale.size_error(error),
+        }
+    }
+}
+
+impl SizeForm {
+    fn new(cfg: &Config) -> Self {
+        SizeForm {
+            rows: Mutable::new(cfg.rows.to_string()),
+            columns: Mutable::new(cfg.columns.to_string()),
+            set_size: Mutable::new(cfg.set_size),
+            error: Mutable::new(None),
+        }
+    }
+
+    fn preset(&self, app: Arc<App>, rows: usize, columns: usize) {
+        self.rows.set(rows.to_string());
+        self.columns.set(columns.to_string());
+        self.apply(app);
+    }
+
+    fn set_matching(&self, app: Arc<App>, set_size: usize) {
+        self.set_size.set(set_size);
+        self.apply(app);
+    }
+
+    fn apply(&self, app: Arc<App>) {
+        let rows = self.rows.lock_ref().trim().parse();
+        let columns = self.columns.lock_ref().trim().parse();
+
+        let result = match (rows, columns) {
+            (Ok(rows), Ok(columns)) => {
+                App::change_size(app, rows, columns, self.set_size.get()).map_err(SizeProblem::Size)
+            }
+            _ => Err(SizeProblem::NotNumbers),
+        };
+        self.error.set(result.err());
+    }
+
+    fn render_input(self: &Arc<Self>, app: Arc<App>, label: &'static str, value: &Mutable<String>) -> Dom {
+        let form = self.clone();
+        html!("input" => HtmlInputElement, {
+            .class("config_input")
+            .attr("type", "number")
+            .attr("min", &MIN_SIDE.to_string())
+            .attr("max", &MAX_SIDE.to_string())
+            .attr_signal("aria-label", app.t_signal(label))
+            .prop_signal("value", value.signal_cloned())
+            .with_node!(input => {
+                .event(clone!(app, value => move |_: events::Input| {
+                    value.set(input.value());
+                    form.apply(app.clone());
+                }))
+            })
+        })
+    }
+}
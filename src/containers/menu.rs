@@ -0,0 +1,145 @@
+use dominator::{clone, events, html, Dom};
+use futures_signals::map_ref;
+use futures_signals::signal::SignalExt;
+use std::sync::Arc;
+
+use crate::{App, Discard, GameStates, Menu};
+
+/// The pause menu, which is also the only way to Restart or start a New
+/// Game on narrow screens.
+pub struct MenuScreen;
+
+impl MenuScreen {
+    pub fn render(&self, app: Arc<App>) -> Dom {
+        let base = "menu";
+        html! {"section", {
+            .class(base)
+            .global_event(clone!(app => move |e: events::KeyDown| {
+                if e.key() != "Escape" || app.state.get() != GameStates::Playing {
+                    return;
+                }
+                if app.menu.get() == Menu::Closed {
+                    App::open_menu(app.clone());
+                } else {
+                    App::close_menu(app.clone());
+                }
+            }))
+            .child_signal(map_ref! {
+                let state = app.state(),
+                let menu = app.menu.signal() =>
+                (*state, *menu)
+            }.map(clone!(app => move |(state, menu)| match (state, menu) {
+                (GameStates::Playing, Menu::Open) => Some(Self::render_menu(app.clone())),
+                (GameStates::Playing, Menu::Confirm(discard)) => Some(Self::render_confirm(app.clone(), discard)),
+                _ => None,
+            })))
+        }}
+    }
+
+    fn render_menu(app: Arc<App>) -> Dom {
+        let base = "menu";
+        let online = app.online.lock_ref().is_some();
+
+        html! {"div", {
+            .class(format!("{}_modal", base))
+            .attr("role", "dialog")
+            .attr("aria-modal", "true")
+            .attr_signal("aria-label", app.t_signal("menu.label"))
+            .children(&mut [
+                html!{"h1", {
+                    .class(format!("{}_title", base))
+                    .text_signal(app.t_signal(if online { "menu.label" } else { "menu.paused" }))
+                }},
+                html!{"p", {
+                    .class(format!("{}_note", base))
+                    .visible(online)
+                    .text_signal(app.t_signal("menu.online"))
+                }},
+                html!{"button", {
+                    .class("btn")
+                    .class("bg_orange")
+                    .focused(true)
+                    .text_signal(app.t_signal("menu.resume"))
+                    .event(clone!(app => move |_: events::Click| {
+                        App::close_menu(app.clone());
+                    }))
+                }},
+                html!{"button", {
+                    .class("btn")
+                    .class("bg_gray_100")
+                    .visible(!online)
+                    .text_signal(app.t_signal("game.restart"))
+                    .event(clone!(app => move |_: events::Click| {
+                        App::discard(app.clone(), Discard::Restart);
+                    }))
+                }},
+                html!{"button", {
+                    .class("btn")
+                    .class("bg_gray_100")
+                    .text_signal(app.t_signal("game.new"))
+                    .event(clone!(app => move |_: events::Click| {
+                        App::discard(app.clone(), Discard::NewGame);
+                    }))
+                }},
+                html!{"button", {
+                    .class("btn")
+                    .class("bg_gray_100")
+                    .attr_signal("aria-pressed", app.settings.signal_ref(|s| if s.muted { "true" } else { "false" }))
+                    .text_signal(map_ref! {
+                        let locale = app.locale_signal(),
+                        let muted = app.settings.signal_ref(|s| s.muted) =>
+                        locale.t(if *muted { "menu.unmute" } else { "menu.mute" })
+                    })
+                    .event(clone!(app => move |_: events::Click| {
+                        App::change_settings(app.clone(), |settings| settings.muted = !settings.muted);
+                    }))
+                }},
+            ])
+        }}
+    }
+
+    /// Asks before the game in progress is thrown away.
+    fn render_confirm(app: Arc<App>, discard: Discard) -> Dom {
+        let base = "menu";
+        let online = app.online.lock_ref().is_some();
+        let id = match discard {
+            Discard::Restart => "confirm.restart",
+            Discard::NewGame if online => "confirm.leave",
+            Discard::NewGame => "confirm.new",
+        };
+
+        html! {"div", {
+            .class(format!("{}_modal", base))
+            .attr("role", "alertdialog")
+            .attr("aria-modal", "true")
+            .attr_signal("aria-label", app.t_signal(id))
+            .children(&mut [
+                html!{"h1", {
+                    .class(format!("{}_title", base))
+                    .text_signal(app.t_signal(id))
+                }},
+                html!{"p", {
+                    .class(format!("{}_note", base))
+                    .text_signal(app.localized(move |locale| locale.t(&format!("{}.text", id))))
+                }},
+                html!{"button", {
+                    .class("btn")
+                    .class("bg_orange")
+                    .text_signal(app.localized(move |locale| locale.t(&format!("{}.action", id))))
+                    .event(clone!(app => move |_: events::Click| {
+                        App::confirm_discard(app.clone(), discard);
+                    }))
+                }},
+                html!{"button", {
+                    .class("btn")
+                    .class("bg_gray_100")
+                    .focused(true)
+                    .text_signal(app.t_signal("confirm.keep"))
+                    .event(clone!(app => move |_: events::Click| {
+                        App::close_menu(app.clone());
+                    }))
+                }},
+            ])
+        }}
+    }
+}
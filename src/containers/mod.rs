@@ -1 +1,4 @@
 pub mod initial;
+pub mod menu;
+pub mod replay;
+pub mod results;
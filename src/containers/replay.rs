@@ -0,0 +1,314 @@
+use dominator::{clone, events, html, Dom};
+use futures_signals::{
+    map_ref,
+    signal::{Mutable, SignalExt},
+    signal_vec::MutableVec,
+};
+use gloo_timers::future::TimeoutFuture;
+use std::sync::Arc;
+
+use crate::engine::replay::{Frame, Replay};
+use crate::engine::{GameEvent, Phase};
+use crate::replay_file::ReplayFile;
+use crate::{format_time, render_board, render_stat, App, Card, CardState, Config, GameStates};
+
+/// Playback speeds offered, as multiples of the pace of the real game.
+const SPEEDS: [f64; 4] = [0.5, 1.0, 2.0, 4.0];
+
+/// Bounds for the wait between two frames during playback, in milliseconds,
+/// so long thinking pauses don't stall the replay.
+const MIN_DELAY: f64 = 150.0;
+const MAX_DELAY: f64 = 2000.0;
+
+pub struct ReplayScreen;
+
+/// A shared or just finished game dealt again from its seed, and where
+/// playback is.
+struct Viewer {
+    config: Mutable<Config>,
+    players: Vec<String>,
+    replay: Replay,
+    frame: Mutable<usize>,
+    playing: Mutable<bool>,
+    speed: Mutable<f64>,
+    cards: MutableVec<Arc<Card>>,
+}
+
+impl Viewer {
+    fn new(file: &ReplayFile) -> Arc<Self> {
+        let replay = Replay::new(file.config.setup(), &file.moves);
+        let cards = replay.frames()[0]
+            .game
+            .cards()
+            .iter()
+            .enumerate()
+            .map(|(i, slot)| Arc::new(Card::new(slot.value, i)))
+            .collect();
+
+        Arc::new(Viewer {
+            config: Mutable::new(file.config.clone()),
+            players: file.players.clone(),
+            replay,
+            frame: Mutable::new(0),
+            playing: Mutable::new(false),
+            speed: Mutable::new(1.0),
+            cards: MutableVec::new_with_values(cards),
+        })
+    }
+
+    fn current(&self) -> &Frame {
+        &self.replay.frames()[self.frame.get()]
+    }
+
+    fn show(&self, index: usize) {
+        let index = index.min(self.replay.last());
+        self.frame.set(index);
+
+        let states = card_states(&self.replay.frames()[index]);
+        for (card, state) in self.cards.lock_ref().iter().zip(states) {
+            card.state.set(state);
+        }
+    }
+
+    fn step_forward(&self) {
+        self.show(self.frame.get() + 1);
+    }
+
+    fn step_back(&self) {
+        self.show(self.frame.get().saturating_sub(1));
+    }
+
+    fn toggle_playing(&self) {
+        if !self.playing.get() && self.frame.get() == self.replay.last() {
+            self.show(0);
+        }
+        self.playing.set(!self.playing.get());
+    }
+
+    /// How long the current frame stayed on screen in the real game.
+    fn delay(&self) -> f64 {
+        let frames = self.replay.frames();
+        let index = self.frame.get();
+        let gap = match frames.get(index + 1) {
+            Some(next) => next.at - frames[index].at,
+            None => 0.0,
+        };
+        (gap / self.speed.get()).clamp(MIN_DELAY, MAX_DELAY)
+    }
+
+    /// One playback step; stops on the last frame.
+    fn advance(&self) {
+        if self.frame.get() >= self.replay.last() {
+            self.playing.set(false);
+        } else {
+            self.step_forward();
+        }
+    }
+}
+
+/// Cards at rest take their face; the set just turned over shows whether it
+/// matched, like on the live board.
+fn card_states(frame: &Frame) -> Vec<CardState> {
+    let mut states: Vec<CardState> = frame
+        .game
+        .cards()
+        .iter()
+        .map(|slot| CardState::from_face(slot.face))
+        .collect();
+
+    for event in &frame.events {
+        let (positions, state) = match event {
+            GameEvent::Matched { positions, .. } => (positions, CardState::Fine),
+            GameEvent::Mismatched { positions, .. } => (positions, CardState::Wrong),
+            GameEvent::Hinted { positions, .. } => (positions, CardState::Hinted),
+            _ => continue,
+        };
+        for &p in positions {
+            states[p] = state;
+        }
+    }
+    states
+}
+
+impl ReplayScreen {
+    pub fn render(&self, app: Arc<App>) -> Dom {
+        html! {"section", {
+            .class("game")
+            .class("replay")
+            .visible_signal(app.state().map(|s| s == GameStates::Replay))
+            .child_signal(app.replay.signal_cloned().map(clone!(app => move |file| {
+                file.map(|file| Self::render_viewer(app.clone(), Viewer::new(&file)))
+            })))
+        }}
+    }
+
+    fn render_viewer(app: Arc<App>, viewer: Arc<Viewer>) -> Dom {
+        let base = "game";
+
+        html! {"div", {
+            .class("replay_viewer")
+            .future(clone!(viewer => async move {
+                loop {
+                    let delay = if viewer.playing.get() { viewer.delay() } else { MIN_DELAY };
+                    TimeoutFuture::new(delay as u32).await;
+                    if viewer.playing.get() {
+                        viewer.advance();
+                    }
+                }
+            }))
+            .children(&mut [
+                html!{"div", {
+                    .class(format!("{}_top", base))
+                    .children(&mut [
+                        html!{"div", {
+                            .children(&mut [
+                                html!{"h1", {
+                                    .class(format!("{}_title", base))
+                                    .text_signal(app.t_signal("replay.title"))
+                                }},
+                                html!{"p", {
+                                    .class(format!("{}_seed", base))
+                                    .text_signal(clone!(viewer => map_ref! {
+                                        let locale = app.locale_signal(),
+                                        let i = viewer.frame.signal() =>
+                                        locale.t_with("replay.step", &[
+                                            ("step", i),
+                                            ("last", &viewer.replay.last()),
+                                            ("time", &format_time(viewer.current().at)),
+                                        ])
+                                    }))
+                                }},
+                            ])
+                        }},
+                        html!{"div", {
+                            .class(format!("{}_options", base))
+                            .children(&mut [
+                                html!{"button", {
+                                    .class("btn")
+                                    .class("bg_gray_100")
+                                    .text_signal(map_ref! {
+                                        let locale = app.locale_signal(),
+                                        let over = app.game.signal_ref(|g| g.phase() == Phase::Over) =>
+                                        locale.t(if *over { "replay.back_results" } else { "replay.back_setup" })
+                                    })
+                                    .event(clone!(app => move |_: events::Click| {
+                                        App::close_replay(app.clone());
+                                    }))
+                                }},
+                            ])
+                        }},
+                    ])
+                }},
+
+                render_board(app.clone(), &viewer.config, &viewer.cards, false),
+
+                Self::render_controls(app.clone(), viewer.clone()),
+
+                html!{"div", {
+                    .class(format!("{}_players", base))
+                    .children(&mut [
+                        html!{"ul", {
+                            .class("players_list")
+                            .class(match viewer.players.len() {
+                                1 => "one_p",
+                                2 => "two_p",
+                                3 => "three_p",
+                                _ => "four_p",
+                            })
+                            .children(Self::render_players(app.clone(), viewer.clone()))
+                        }},
+                    ])
+                }},
+            ])
+        }}
+    }
+
+    fn render_controls(app: Arc<App>, viewer: Arc<Viewer>) -> Dom {
+        let base = "replay_controls";
+
+        let button = |text: &str, label: &'static str, action: fn(&Viewer)| {
+            html!{"button", {
+                .class("btn")
+                .class("bg_gray_100")
+                .text(text)
+                .attr_signal("aria-label", app.t_signal(label))
+                .event(clone!(viewer => move |_: events::Click| {
+                    viewer.playing.set(false);
+                    action(&viewer);
+                }))
+            }}
+        };
+
+        html! {"div", {
+            .class(base)
+            .children(&mut [
+                button("⏮", "replay.first", |v| v.show(0)),
+                button("◀", "replay.back", Viewer::step_back),
+                html!{"button", {
+                    .class("btn")
+                    .class("bg_orange")
+                    .text_signal(map_ref! {
+                        let locale = app.locale_signal(),
+                        let playing = viewer.playing.signal() =>
+                        locale.t(if *playing { "replay.pause" } else { "replay.play" })
+                    })
+                    .event(clone!(viewer => move |_: events::Click| {
+                        viewer.toggle_playing();
+                    }))
+                }},
+                button("▶", "replay.forward", Viewer::step_forward),
+                button("⏭", "replay.last", |v| v.show(v.replay.last())),
+                html!{"div", {
+                    .class(format!("{}__speeds", base))
+                    .attr("role", "group")
+                    .attr_signal("aria-label", app.t_signal("replay.speed"))
+                    .children(SPEEDS.iter().map(|&speed| html!{"button", {
+                        .class("btn")
+                        .class("bg_gray_100")
+                        .class_signal("selected", viewer.speed.signal().map(move |s| s == speed))
+                        .text(&format!("{}x", speed))
+                        .event(clone!(viewer => move |_: events::Click| {
+                            viewer.speed.set(speed);
+                        }))
+                    }}))
+                }},
+            ])
+        }}
+    }
+
+    fn render_players(app: Arc<App>, viewer: Arc<Viewer>) -> Vec<Dom> {
+        let players = viewer.replay.frames()[0].game.players().len();
+
+        if players == 1 {
+            return vec![
+                render_stat(app.t_signal("stat.time"), viewer.frame.signal().map(clone!(viewer => move |_| {
+                    format_time(viewer.current().at)
+                }))),
+                render_stat(app.t_signal("stat.moves"), viewer.frame.signal().map(clone!(viewer => move |_| {
+                    format!("{}", viewer.current().game.players()[0].moves)
+                }))),
+            ];
+        }
+
+        (0..players)
+            .map(|id| html!{"li", {
+                .class("players_list__item")
+                .class_signal("in_turn", viewer.frame.signal().map(clone!(viewer => move |_| {
+                    viewer.current().game.turn() == id
+                })))
+                .children(&mut [
+                    html!{"p", {
+                        .class("player-name")
+                        .text(&viewer.players[id])
+                    }},
+                    html!{"p", {
+                        .class("player-score")
+                        .text_signal(viewer.frame.signal().map(clone!(viewer => move |_| {
+                            format!("{}", viewer.current().game.players()[id].score)
+                        })))
+                    }},
+                ])
+            }})
+            .collect()
+    }
+}
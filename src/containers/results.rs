@@ -0,0 +1,290 @@
+use dominator::{clone, events, html, Dom};
+use futures_signals::map_ref;
+use futures_signals::signal::{always, Mutable, Signal, SignalExt};
+use std::pin::Pin;
+use std::sync::Arc;
+use wasm_bindgen_futures::spawn_local;
+
+use crate::engine::scoring::Scoring;
+use crate::{capitalize, daily, files, format_time, today, App, GameStates};
+
+pub struct ResultsScreen;
+
+/// Text that follows the language; results mix several kinds of it.
+type Text = Pin<Box<dyn Signal<Item = String>>>;
+
+impl ResultsScreen {
+    pub fn render(&self, app: Arc<App>) -> Dom {
+        let base = "results";
+        html! {"section", {
+            .class(base)
+            .visible_signal(app.state().map(|s| s == GameStates::Over))
+            .child_signal(app.state().map(clone!(app => move |s| {
+                if s == GameStates::Over {
+                    Some(Self::render_modal(app.clone()))
+                } else {
+                    None
+                }
+            })))
+        }}
+    }
+
+    fn render_modal(app: Arc<App>) -> Dom {
+        let base = "results";
+        let solo = app.config.lock_ref().players == 1;
+        let export_error = Mutable::new(false);
+
+        let (title, subtitle, mut rows): (Text, _, _) = if solo {
+            (
+                app.t_signal("results.solo").boxed_local(),
+                "results.solo.subtitle",
+                Self::render_solo(app.clone()),
+            )
+        } else {
+            let winners = app.game.lock_ref().winners();
+            let title = if winners.len() > 1 {
+                app.t_signal("results.tie").boxed_local()
+            } else {
+                map_ref! {
+                    let locale = app.locale_signal(),
+                    let name = app.players.lock_ref()[winners[0]].name.signal_cloned() =>
+                    locale.t_with("results.wins", &[("name", name)])
+                }
+                .boxed_local()
+            };
+            (title, "results.subtitle", Self::render_ranking(app.clone()))
+        };
+
+        html! {"div", {
+            .class(format!("{}_modal", base))
+            .children(&mut [
+                html!{"h1", {
+                    .class(format!("{}_title", base))
+                    .text_signal(title)
+                }},
+                html!{"p", {
+                    .class(format!("{}_subtitle", base))
+                    .text_signal(app.t_signal(subtitle))
+                }},
+                html!{"ul", {
+                    .class(format!("{}_list", base))
+                    .children(&mut rows)
+                }},
+                html!{"p", {
+                    .class(format!("{}_record", base))
+                    .visible(!solo && app.improvement.get().any())
+                    .text_signal(app.t_signal("results.best"))
+                }},
+                html!{"p", {
+                    .class(format!("{}_practice", base))
+                    .visible(app.practice.get())
+                    .text_signal(app.t_signal("results.practice"))
+                }},
+            ])
+            .children(Self::render_daily(app.clone()))
+            .children(&mut [
+                html!{"p", {
+                    .class(format!("{}_error", base))
+                    .attr("role", "alert")
+                    .visible_signal(export_error.signal())
+                    .text_signal(app.t_signal("results.download_failed"))
+                }},
+                html!{"div", {
+                    .class(format!("{}_options", base))
+                    .children(&mut [
+                        html!{"button", {
+                            .class("btn")
+                            .class("bg_orange")
+                            .visible(app.online.lock_ref().is_none())
+                            .text_signal(app.t_signal("game.restart"))
+                            .event(clone!(app => move |_: events::Click| {
+                                App::play_again(app.clone());
+                            }))
+                        }},
+                        html!{"button", {
+                            .class("btn")
+                            .class("bg_gray_100")
+                            .text_signal(app.t_signal("results.setup"))
+                            .event(clone!(app => move |_: events::Click| {
+                                App::setup_new_game(app.clone());
+                            }))
+                        }},
+                        html!{"button", {
+                            .class("btn")
+                            .class("bg_gray_100")
+                            .text_signal(app.t_signal("results.watch"))
+                            .event(clone!(app => move |_: events::Click| {
+                                App::watch_replay(app.clone());
+                            }))
+                        }},
+                        html!{"button", {
+                            .class("btn")
+                            .class("bg_gray_100")
+                            .text_signal(app.t_signal("results.download"))
+                            .event(clone!(app, export_error => move |_: events::Click| {
+                                let failed = app.export_replay().is_err();
+                                export_error.set(failed);
+                            }))
+                        }},
+                    ])
+                }},
+            ])
+        }}
+    }
+
+    /// Streak and the text to share for a finished daily challenge.
+    fn render_daily(app: Arc<App>) -> Option<Dom> {
+        let base = "results_daily";
+        let cfg = app.config.get_cloned();
+        let day = cfg.daily?;
+        let daily = app.daily.lock_ref();
+        let result = daily.get(day)?.clone();
+        let streak = daily.streak(today()) as usize;
+        let copied = Mutable::new(None);
+        let summary = clone!(cfg, result => move |locale| daily::summary(locale, day, &cfg, &result));
+
+        Some(html! {"div", {
+            .class(base)
+            .children(&mut [
+                html!{"p", {
+                    .class(format!("{}__streak", base))
+                    .text_signal(app.localized(move |locale| locale.t_count("results.streak", streak, &[])))
+                }},
+                html!{"p", {
+                    .class(format!("{}__note", base))
+                    .visible(!app.daily_scored.get())
+                    .text_signal(app.t_signal("results.first_only"))
+                }},
+                html!{"textarea", {
+                    .class(format!("{}__summary", base))
+                    .attr("readonly", "")
+                    .attr_signal("rows", app.localized(clone!(summary => move |locale| summary(locale).lines().count().to_string())))
+                    .attr_signal("aria-label", app.t_signal("results.share"))
+                    .prop_signal("value", app.localized(summary.clone()))
+                }},
+                html!{"button", {
+                    .class("btn")
+                    .class("bg_gray_100")
+                    .text_signal(map_ref! {
+                        let locale = app.locale_signal(),
+                        let copied = copied.signal() =>
+                        locale.t(match copied {
+                            None => "results.copy",
+                            Some(true) => "results.copied",
+                            Some(false) => "results.copy_failed",
+                        })
+                    })
+                    .event(clone!(app, copied => move |_: events::Click| {
+                        let summary = summary(app.locale());
+                        spawn_local(clone!(copied => async move {
+                            copied.set(Some(files::copy(&summary).await.is_ok()));
+                        }));
+                    }))
+                }},
+            ])
+        }})
+    }
+
+    fn render_solo(app: Arc<App>) -> Vec<Dom> {
+        let player = app.players.lock_ref()[0].clone();
+        let time = player.time.as_ref().map(|t| t.get()).unwrap_or(0.0);
+        let moves = player.moves.get();
+        let improvement = app.improvement.get();
+
+        let label = |id: &'static str, record: bool| {
+            app.localized(move |locale| {
+                if record {
+                    locale.t_with("results.new_best", &[("label", &locale.t(id))])
+                } else {
+                    locale.t(id)
+                }
+            })
+        };
+
+        let mut rows = vec![
+            Self::render_row(label("results.time", improvement.time), always(format_time(time)), improvement.time),
+            Self::render_row(
+                label("results.moves", improvement.moves),
+                app.localized(move |locale| locale.t_count("results.moves_value", moves, &[])),
+                improvement.moves,
+            ),
+        ];
+        if app.config.lock_ref().hints > 0 {
+            let hints = player.hints.get();
+            let penalty = app.game.lock_ref().penalty_time();
+            let value = format!("{} (+{})", hints, format_time(penalty));
+            rows.push(Self::render_row(app.t_signal("results.hints"), always(value), false));
+        }
+        if app.config.lock_ref().scoring != Scoring::Classic {
+            let game = app.game.lock_ref();
+            let (breakdown, set_size) = (game.players()[0].breakdown, game.setup().set_size);
+            let score = player.score.get();
+            rows.push(Self::render_row(
+                app.t_signal("results.points"),
+                app.localized(move |locale| format!("{} ({})", score, locale.breakdown(&breakdown, set_size))),
+                false,
+            ));
+        }
+        rows
+    }
+
+    fn render_ranking(app: Arc<App>) -> Vec<Dom> {
+        let game = app.game.lock_ref();
+        let winners = game.winners();
+        let set_size = game.setup().set_size;
+        let classic = game.setup().scoring == Scoring::Classic;
+        let players = app.players.lock_ref();
+
+        game.ranking()
+            .into_iter()
+            .map(|id| {
+                let winner = winners.contains(&id);
+                let name = map_ref! {
+                    let locale = app.locale_signal(),
+                    let name = players[id].name.signal_cloned() =>
+                    if winner { locale.t_with("results.winner", &[("name", name)]) } else { name.clone() }
+                };
+                let score = players[id].score.get();
+                let breakdown = game.players()[id].breakdown;
+                let hints = players[id].hints.get();
+                let score = app.localized(move |locale| {
+                    let mut text = if classic {
+                        format!("{} {}", score, capitalize(&locale.set_name(set_size, score as usize)))
+                    } else {
+                        locale.t_count("results.points_value", score as usize, &[])
+                    };
+                    if breakdown.bonus > 0 || breakdown.penalty > 0 {
+                        text.push_str(&format!(" · {}", locale.breakdown(&breakdown, set_size)));
+                    }
+                    if hints > 0 {
+                        text.push_str(&format!(" · {}", locale.t_count("results.hints_used", hints, &[])));
+                    }
+                    text
+                });
+                Self::render_row(name, score, winner)
+            })
+            .collect()
+    }
+
+    fn render_row<L, V>(label: L, value: V, highlight: bool) -> Dom
+    where
+        L: Signal<Item = String> + 'static,
+        V: Signal<Item = String> + 'static,
+    {
+        let base = "results_list";
+        html! {"li", {
+            .class(format!("{}__item", base))
+            .apply_if(highlight, |dom| dom.class("winner"))
+            .children(&mut [
+                html!{"span", {
+                    .class(format!("{}__label", base))
+                    .text_signal(label)
+                }},
+                html!{"span", {
+                    .class(format!("{}__value", base))
+                    .text_signal(value)
+                }},
+            ])
+        }}
+    }
+}
@@ -0,0 +1,234 @@
+//! The daily challenge: one solo board per calendar day, the same for
+//! everyone, and the first attempt of each day kept in `localStorage`. An
+//! attempt starts when the board is dealt, so leaving it doesn't earn
+//! another try.
+//!
+//! Days are counted from 1970-01-01 in the player's own calendar, so the
+//! board changes at local midnight and the seed only depends on the date.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::engine::replay::Entry;
+use crate::engine::scoring::Scoring;
+use crate::engine::turns::TurnRule;
+use crate::engine::GameEvent;
+use crate::i18n::Locale;
+use crate::{format_time, profiles, Config, GameTheme};
+
+pub const STORAGE_KEY: &str = "daily";
+
+pub const ROWS: usize = 6;
+pub const COLUMNS: usize = 6;
+
+/// The scored attempt of one day. `outcomes` has one entry per move, `true`
+/// for a match.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DailyResult {
+    pub moves: usize,
+    pub time: f64,
+    pub outcomes: Vec<bool>,
+    /// Left before the board was cleared, or cleared with moves undone.
+    #[serde(default)]
+    pub abandoned: bool,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Daily {
+    results: BTreeMap<i64, DailyResult>,
+}
+
+/// Day number of a date in the proleptic Gregorian calendar.
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let month = month as i64;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// `YYYY-MM-DD` for a day number.
+pub fn date(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// A short seed scrambled from the day so neighbouring days look unrelated.
+pub fn seed(day: i64) -> u64 {
+    let mut x = (day as u64).wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    (x ^ (x >> 31)) % 1_000_000
+}
+
+/// The board of `day`. Only the theme and hints are left to the player.
+pub fn config(day: i64, theme: GameTheme, hints: usize) -> Config {
+    Config {
+        theme,
+        players: 1,
+        rows: ROWS,
+        columns: COLUMNS,
+        set_size: 2,
+        seed: seed(day),
+        daily: Some(day),
+        hints,
+        computers: [None; profiles::SEATS],
+        scoring: Scoring::Classic,
+        turns: TurnRule::KeepOnMatch,
+        shot_clock: None,
+    }
+}
+
+/// Whether every move in `log` found a set, in order.
+pub fn outcomes(log: &[Entry]) -> Vec<bool> {
+    log.iter()
+        .filter_map(|entry| match entry.event {
+            GameEvent::Matched { .. } => Some(true),
+            GameEvent::Mismatched { .. } => Some(false),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Text for chat that tells how the day went without showing the board.
+pub fn summary(locale: Locale, day: i64, cfg: &Config, result: &DailyResult) -> String {
+    let grid: Vec<String> = result
+        .outcomes
+        .chunks(COLUMNS)
+        .map(|row| row.iter().map(|&hit| if hit { "🟩" } else { "🟥" }).collect())
+        .collect();
+
+    let mut line = locale.t_with(
+        "daily.share.line",
+        &[
+            ("rows", &cfg.rows),
+            ("columns", &cfg.columns),
+            ("moves", &locale.t_count("moves", result.moves, &[])),
+            ("time", &format_time(result.time)),
+        ],
+    );
+    if result.abandoned {
+        line = locale.t_with("daily.share.abandoned", &[("line", &line)]);
+    }
+    format!(
+        "{}\n{}\n{}",
+        locale.t_with("daily.share.title", &[("date", &date(day))]),
+        line,
+        grid.join("\n")
+    )
+}
+
+impl Daily {
+    pub fn get(&self, day: i64) -> Option<&DailyResult> {
+        self.results.get(&day)
+    }
+
+    /// Claims the attempt of `day` for a board just dealt, unless the day
+    /// already has one. Until `update` says otherwise it counts as given up
+    /// with no moves. Returns whether it was claimed.
+    pub fn start(&mut self, day: i64) -> bool {
+        if self.results.contains_key(&day) {
+            return false;
+        }
+        let result = DailyResult {
+            abandoned: true,
+            ..Default::default()
+        };
+        self.results.insert(day, result);
+        true
+    }
+
+    /// Records how the claimed attempt of `day` is going.
+    pub fn update(&mut self, day: i64, result: DailyResult) {
+        self.results.insert(day, result);
+    }
+
+    /// Days in a row with a result up to `today`. Not having played today
+    /// yet doesn't break the streak.
+    pub fn streak(&self, today: i64) -> u32 {
+        let mut day = if self.results.contains_key(&today) { today } else { today - 1 };
+        let mut streak = 0;
+        while self.results.contains_key(&day) {
+            streak += 1;
+            day -= 1;
+        }
+        streak
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(outcomes: Vec<bool>) -> DailyResult {
+        DailyResult {
+            moves: outcomes.len(),
+            time: 75_000.0,
+            outcomes,
+            abandoned: false,
+        }
+    }
+
+    #[test]
+    fn days_and_dates_agree() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(date(0), "1970-01-01");
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+
+        let day = days_from_civil(2024, 2, 29);
+        assert_eq!(date(day), "2024-02-29");
+        assert_eq!(date(day + 1), "2024-03-01");
+        assert_ne!(seed(day), seed(day + 1));
+    }
+
+    #[test]
+    fn only_the_first_attempt_counts() {
+        let mut daily = Daily::default();
+
+        assert!(daily.start(10));
+        assert!(daily.get(10).unwrap().abandoned);
+        daily.update(10, result(vec![true]));
+        assert!(!daily.start(10));
+        assert_eq!(daily.get(10), Some(&result(vec![true])));
+    }
+
+    #[test]
+    fn streak_survives_until_today_is_over() {
+        let mut daily = Daily::default();
+        for day in [3, 5, 6, 7] {
+            daily.start(day);
+        }
+
+        assert_eq!(daily.streak(7), 3);
+        assert_eq!(daily.streak(8), 3);
+        assert_eq!(daily.streak(9), 0);
+    }
+
+    #[test]
+    fn summary_shows_moves_but_not_cards() {
+        let day = days_from_civil(2026, 10, 18);
+        let outcomes = vec![false, true, false, false, true, true, true];
+        let cfg = config(day, GameTheme::Numbers, 0);
+        let text = summary(Locale::En, day, &cfg, &result(outcomes.clone()));
+        assert_eq!(text, "Memory Daily 2026-10-18\n6x6 · 7 moves · 1:15\n🟥🟩🟥🟥🟩🟩\n🟩");
+
+        let left = DailyResult {
+            abandoned: true,
+            ..result(outcomes)
+        };
+        assert_eq!(
+            summary(Locale::Es, day, &cfg, &left).lines().nth(1),
+            Some("6x6 · 7 movimientos · 1:15 · abandonado")
+        );
+    }
+}
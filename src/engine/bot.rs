@@ -0,0 +1,231 @@
+//! Computer opponents.
+//!
+//! A `Bot` watches every flip on the board, like a person at the table
+//! would, and remembers where it saw each value. `Difficulty` decides how
+//! many flips back that memory reaches and how often a remembered card is
+//! actually recalled when the bot picks its next flip.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::{Face, Game, GameEvent};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Sighting {
+    value: usize,
+    flip: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Bot {
+    difficulty: Difficulty,
+    seen: BTreeMap<usize, Sighting>,
+    flips: usize,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        }
+    }
+
+    /// How many flips ago a card can have been seen and still be recalled.
+    fn span(&self) -> usize {
+        match self {
+            Difficulty::Easy => 4,
+            Difficulty::Medium => 12,
+            Difficulty::Hard => usize::MAX,
+        }
+    }
+
+    /// Chance of recalling a card that is within `span`.
+    fn recall(&self) -> f64 {
+        match self {
+            Difficulty::Easy => 0.5,
+            Difficulty::Medium => 0.8,
+            Difficulty::Hard => 1.0,
+        }
+    }
+}
+
+impl Bot {
+    pub fn new(difficulty: Difficulty) -> Self {
+        Bot {
+            difficulty,
+            seen: BTreeMap::new(),
+            flips: 0,
+        }
+    }
+
+    /// Takes note of cards turned up and of sets leaving the board.
+    pub fn watch(&mut self, game: &Game, events: &[GameEvent]) {
+        for event in events {
+            match event {
+                GameEvent::Flipped { position } => {
+                    self.flips += 1;
+                    let value = game.cards()[*position].value;
+                    self.seen.insert(*position, Sighting { value, flip: self.flips });
+                }
+                GameEvent::Settled { positions } => {
+                    for p in positions {
+                        self.seen.remove(p);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn recall<R: Rng>(&self, position: usize, rng: &mut R) -> Option<usize> {
+        let sighting = self.seen.get(&position)?;
+        let fresh = self.flips - sighting.flip < self.difficulty.span();
+        if fresh && rng.gen_bool(self.difficulty.recall()) {
+            Some(sighting.value)
+        } else {
+            None
+        }
+    }
+
+    /// The next card to flip: a remembered card completing the set in
+    /// progress, or starting a set it knows in full, otherwise one it has
+    /// not seen. `None` when nothing can be flipped.
+    pub fn choose<R: Rng>(&self, game: &Game, rng: &mut R) -> Option<usize> {
+        let cards = game.cards();
+        let down: Vec<usize> = (0..cards.len()).filter(|&p| cards[p].face == Face::Down).collect();
+        let known: Vec<(usize, usize)> = down
+            .iter()
+            .filter_map(|&p| self.recall(p, rng).map(|value| (p, value)))
+            .collect();
+
+        let up = (0..cards.len()).find(|&p| cards[p].face == Face::Up);
+        let target = match up {
+            Some(p) => Some(cards[p].value),
+            None => known
+                .iter()
+                .map(|&(_, value)| value)
+                .find(|&value| known.iter().filter(|&&(_, v)| v == value).count() >= game.setup().set_size),
+        };
+        if let Some(value) = target {
+            if let Some(&(p, _)) = known.iter().find(|&&(_, v)| v == value) {
+                return Some(p);
+            }
+        }
+
+        let unknown: Vec<usize> = down
+            .iter()
+            .copied()
+            .filter(|p| !known.iter().any(|&(k, _)| k == *p))
+            .collect();
+        unknown.choose(rng).or_else(|| down.choose(rng)).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::Setup;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn game() -> Game {
+        Game::new(Setup {
+            cards: 16,
+            players: 2,
+            set_size: 2,
+            seed: 11,
+            hints: 0,
+            scoring: Default::default(),
+            turns: Default::default(),
+        })
+    }
+
+    fn pair_of(game: &Game, value: usize) -> Vec<usize> {
+        (0..game.cards().len()).filter(|&p| game.cards()[p].value == value).collect()
+    }
+
+    fn flip(game: &mut Game, bot: &mut Bot, position: usize) {
+        let events = game.flip(position);
+        bot.watch(game, &events);
+    }
+
+    fn resolve(game: &mut Game, bot: &mut Bot) {
+        let events = game.resolve();
+        bot.watch(game, &events);
+    }
+
+    #[test]
+    fn hard_bot_goes_for_a_pair_it_has_seen() {
+        let mut game = game();
+        let mut bot = Bot::new(Difficulty::Hard);
+        let mut rng = StdRng::seed_from_u64(1);
+        let a = pair_of(&game, 0);
+        let b = pair_of(&game, 1);
+
+        flip(&mut game, &mut bot, a[0]);
+        flip(&mut game, &mut bot, b[0]);
+        resolve(&mut game, &mut bot);
+        flip(&mut game, &mut bot, a[1]);
+
+        assert_eq!(bot.choose(&game, &mut rng), Some(a[0]));
+        flip(&mut game, &mut bot, a[0]);
+        resolve(&mut game, &mut bot);
+
+        flip(&mut game, &mut bot, b[1]);
+        assert_eq!(bot.choose(&game, &mut rng), Some(b[0]));
+    }
+
+    #[test]
+    fn bot_explores_cards_it_has_not_seen() {
+        let mut game = game();
+        let mut bot = Bot::new(Difficulty::Hard);
+        let mut rng = StdRng::seed_from_u64(2);
+        let a = pair_of(&game, 0)[0];
+        let b = pair_of(&game, 1)[0];
+
+        flip(&mut game, &mut bot, a);
+        flip(&mut game, &mut bot, b);
+        resolve(&mut game, &mut bot);
+
+        for _ in 0..20 {
+            let choice = bot.choose(&game, &mut rng).unwrap();
+            assert!(choice != a && choice != b);
+        }
+    }
+
+    #[test]
+    fn easy_bot_forgets_old_flips() {
+        let mut game = game();
+        let mut bot = Bot::new(Difficulty::Easy);
+        let mut rng = StdRng::seed_from_u64(3);
+        let old = pair_of(&game, 0)[0];
+        let other = pair_of(&game, 1)[0];
+
+        flip(&mut game, &mut bot, old);
+        flip(&mut game, &mut bot, other);
+        resolve(&mut game, &mut bot);
+        assert!((0..20).any(|_| bot.recall(old, &mut rng).is_some()));
+
+        for value in 2..4 {
+            let pair = pair_of(&game, value);
+            flip(&mut game, &mut bot, pair[0]);
+            flip(&mut game, &mut bot, pair[1]);
+            resolve(&mut game, &mut bot);
+        }
+
+        assert!((0..20).all(|_| bot.recall(old, &mut rng).is_none()));
+    }
+}
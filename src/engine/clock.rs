@@ -0,0 +1,76 @@
+/// Elapsed play time in milliseconds that can be paused and resumed.
+///
+/// The clock never reads the system time itself; every call takes `now` so
+/// the browser can pass `Date.now()` and tests can pass plain numbers.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Clock {
+    banked: f64,
+    since: Option<f64>,
+    started: bool,
+}
+
+impl Clock {
+    /// A paused clock that already counted `elapsed`, for resumed games.
+    pub fn from_elapsed(elapsed: f64) -> Self {
+        Clock {
+            banked: elapsed,
+            since: None,
+            started: true,
+        }
+    }
+
+    /// Starts counting on the first call; later calls behave like `resume`.
+    pub fn start(&mut self, now: f64) {
+        self.started = true;
+        self.resume(now);
+    }
+
+    /// Continues a started clock. Does nothing before `start`.
+    pub fn resume(&mut self, now: f64) {
+        if self.started && self.since.is_none() {
+            self.since = Some(now);
+        }
+    }
+
+    pub fn pause(&mut self, now: f64) {
+        if let Some(since) = self.since.take() {
+            self.banked += now - since;
+        }
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.started
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.since.is_some()
+    }
+
+    pub fn elapsed(&self, now: f64) -> f64 {
+        self.banked + self.since.map_or(0.0, |since| now - since)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paused_time_is_not_counted() {
+        let mut clock = Clock::default();
+        clock.start(1000.0);
+        clock.pause(3000.0);
+        clock.resume(10_000.0);
+
+        assert_eq!(clock.elapsed(11_000.0), 3000.0);
+    }
+
+    #[test]
+    fn resume_before_start_does_nothing() {
+        let mut clock = Clock::default();
+        clock.resume(500.0);
+
+        assert!(!clock.is_running());
+        assert_eq!(clock.elapsed(2000.0), 0.0);
+    }
+}
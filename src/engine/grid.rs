@@ -0,0 +1,46 @@
+/// Board geometry: positions are laid out row by row, `columns` per row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// Moves `position` one cell in `direction` on a board of `len` cards,
+/// staying put at the edges.
+pub fn step(position: usize, direction: Direction, columns: usize, len: usize) -> usize {
+    let columns = columns.max(1);
+    let column = position % columns;
+
+    let next = match direction {
+        Direction::Up => position.checked_sub(columns),
+        Direction::Down => Some(position + columns),
+        Direction::Left if column > 0 => Some(position - 1),
+        Direction::Right if column + 1 < columns => Some(position + 1),
+        _ => None,
+    };
+
+    next.filter(|&p| p < len).unwrap_or(position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_stop_at_the_edges() {
+        assert_eq!(step(0, Direction::Up, 4, 16), 0);
+        assert_eq!(step(0, Direction::Left, 4, 16), 0);
+        assert_eq!(step(3, Direction::Right, 4, 16), 3);
+        assert_eq!(step(13, Direction::Down, 4, 16), 13);
+    }
+
+    #[test]
+    fn steps_move_across_rows_and_columns() {
+        assert_eq!(step(5, Direction::Up, 4, 16), 1);
+        assert_eq!(step(5, Direction::Down, 4, 16), 9);
+        assert_eq!(step(5, Direction::Left, 4, 16), 4);
+        assert_eq!(step(4, Direction::Right, 6, 36), 5);
+    }
+}
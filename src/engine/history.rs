@@ -0,0 +1,81 @@
+//! Undo and redo for practice games.
+//!
+//! `History` doesn't look at what it keeps: `App` pushes a snapshot before
+//! every attempt and trades the one on screen for an older or newer one.
+
+#[derive(Clone, Debug)]
+pub struct History<T> {
+    undo: Vec<T>,
+    redo: Vec<T>,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        History {
+            undo: vec![],
+            redo: vec![],
+        }
+    }
+}
+
+impl<T> History<T> {
+    /// Remembers `state` before a new move. Whatever was undone so far
+    /// can't be redone any more.
+    pub fn push(&mut self, state: T) {
+        self.undo.push(state);
+        self.redo.clear();
+    }
+
+    /// Trades `current` for the state before the last move.
+    pub fn undo(&mut self, current: T) -> Option<T> {
+        let previous = self.undo.pop()?;
+        self.redo.push(current);
+        Some(previous)
+    }
+
+    /// Trades `current` for the state the last `undo` left.
+    pub fn redo(&mut self, current: T) -> Option<T> {
+        let next = self.redo.pop()?;
+        self.undo.push(current);
+        Some(next)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_and_redo_walk_the_moves() {
+        let mut history = History::default();
+        history.push(0);
+        history.push(1);
+
+        assert_eq!(history.undo(2), Some(1));
+        assert_eq!(history.undo(1), Some(0));
+        assert_eq!(history.undo(0), None);
+        assert_eq!(history.redo(0), Some(1));
+        assert_eq!(history.redo(1), Some(2));
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn a_new_move_drops_what_was_undone() {
+        let mut history = History::default();
+        history.push(0);
+        assert_eq!(history.undo(1), Some(0));
+        assert!(history.can_redo());
+
+        history.push(0);
+        assert!(!history.can_redo());
+        assert!(history.can_undo());
+    }
+}
@@ -0,0 +1,908 @@
+//! Game rules without any DOM, timers or signals.
+//!
+//! `App` owns a `Game`, feeds it the position of every clicked card and turns
+//! the returned `GameEvent`s into signal updates and animations. Once the
+//! reveal animation of a pair is over `App` calls `Game::resolve`, and no
+//! other card can be flipped until it does.
+
+pub mod bot;
+pub mod clock;
+pub mod grid;
+pub mod history;
+pub mod replay;
+pub mod scoring;
+pub mod turns;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use scoring::{Breakdown, Scoring};
+use turns::TurnRule;
+
+/// Smallest and largest number of rows or columns on a board.
+pub const MIN_SIDE: usize = 2;
+pub const MAX_SIDE: usize = 10;
+
+/// How many identical cards can make up a set.
+pub const SET_SIZES: [usize; 3] = [2, 3, 4];
+
+/// Time added to a solo game for every hint, in milliseconds. With more
+/// players a hint costs a point instead.
+pub const HINT_TIME_PENALTY: f64 = 10_000.0;
+
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Face {
+    Down,
+    Up,
+    Matched,
+}
+
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Phase {
+    Playing,
+    Resolving,
+    Over,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameEvent {
+    Flipped { position: usize },
+    Matched { player: usize, positions: Vec<usize> },
+    Mismatched { player: usize, positions: Vec<usize> },
+    Settled { positions: Vec<usize> },
+    Hidden { positions: Vec<usize> },
+    Scored { player: usize, score: u32 },
+    Moved { player: usize, moves: usize },
+    TurnChanged { player: usize },
+    Hinted { player: usize, positions: Vec<usize>, hints: usize },
+    /// The player ran out of time; see `Game::pass`.
+    TimedOut { player: usize },
+    Over,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SizeError {
+    OutOfRange { rows: usize, columns: usize },
+    Uneven { rows: usize, columns: usize, set_size: usize },
+    SetSize(usize),
+}
+
+/// Everything needed to deal a new game.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Setup {
+    pub cards: usize,
+    pub players: usize,
+    pub set_size: usize,
+    pub seed: u64,
+    /// Hints each player may use in a game.
+    #[serde(default)]
+    pub hints: usize,
+    #[serde(default)]
+    pub scoring: Scoring,
+    #[serde(default)]
+    pub turns: TurnRule,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Slot {
+    pub value: usize,
+    pub face: Face,
+    /// Whether the card was ever turned up.
+    #[serde(default)]
+    pub seen: bool,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Tally {
+    pub score: u32,
+    pub moves: usize,
+    #[serde(default)]
+    pub hints: usize,
+    /// What `score` is made of. Games saved before scoring rules only
+    /// have the score.
+    #[serde(default)]
+    pub breakdown: Breakdown,
+    /// Gave up their seat; their turns are skipped from then on.
+    #[serde(default)]
+    pub forfeited: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Game {
+    setup: Setup,
+    cards: Vec<Slot>,
+    players: Vec<Tally>,
+    turn: usize,
+    phase: Phase,
+    selected: Vec<usize>,
+    /// Sets found in a row since the last miss.
+    #[serde(default)]
+    streak: u32,
+    /// When the clock of the attempt under way started: as the last one
+    /// was settled by `resolve_at`, or at its first flip when there was
+    /// none or the turn was passed. Resolving sets aren't timed.
+    #[serde(default)]
+    since: Option<f64>,
+    /// Goes in a row the player in turn has had after finding sets.
+    #[serde(default)]
+    extra: u32,
+}
+
+/// Checks that a `rows` x `columns` board can be dealt in sets of `set_size`.
+pub fn check_size(rows: usize, columns: usize, set_size: usize) -> Result<(), SizeError> {
+    let allowed = MIN_SIDE..=MAX_SIDE;
+    if !allowed.contains(&rows) || !allowed.contains(&columns) {
+        return Err(SizeError::OutOfRange { rows, columns });
+    }
+    if !SET_SIZES.contains(&set_size) {
+        return Err(SizeError::SetSize(set_size));
+    }
+    if !(rows * columns).is_multiple_of(set_size) {
+        return Err(SizeError::Uneven { rows, columns, set_size });
+    }
+    Ok(())
+}
+
+/// Card values in board order, `set_size` copies of each. The same `seed`
+/// always yields the same layout.
+pub fn deal(size: usize, set_size: usize, seed: u64) -> Vec<usize> {
+    let mut values: Vec<usize> = (0..size).map(|i| i % (size / set_size)).collect();
+    values.shuffle(&mut StdRng::seed_from_u64(seed));
+    values
+}
+
+impl Game {
+    pub fn new(setup: Setup) -> Self {
+        let cards = deal(setup.cards, setup.set_size, setup.seed)
+            .into_iter()
+            .map(|value| Slot {
+                value,
+                face: Face::Down,
+                seen: false,
+            })
+            .collect();
+
+        Game {
+            setup,
+            cards,
+            players: vec![Tally::default(); setup.players.max(1)],
+            turn: 0,
+            phase: Phase::Playing,
+            selected: vec![],
+            streak: 0,
+            since: None,
+            extra: 0,
+        }
+    }
+
+    pub fn setup(&self) -> Setup {
+        self.setup
+    }
+
+    pub fn cards(&self) -> &[Slot] {
+        &self.cards
+    }
+
+    pub fn players(&self) -> &[Tally] {
+        &self.players
+    }
+
+    pub fn turn(&self) -> usize {
+        self.turn
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    /// Cards turned so far in the current attempt.
+    pub fn selected(&self) -> &[usize] {
+        &self.selected
+    }
+
+    /// Goes in a row the player in turn has had, see `TurnRule`.
+    pub fn extra_turns(&self) -> u32 {
+        self.extra
+    }
+
+    /// Player ids ordered by score, highest first. Ties keep seat order.
+    pub fn ranking(&self) -> Vec<usize> {
+        let mut ids: Vec<usize> = (0..self.players.len()).collect();
+        ids.sort_by(|a, b| self.players[*b].score.cmp(&self.players[*a].score));
+        ids
+    }
+
+    /// Every player sharing the top score; more than one means a tie.
+    pub fn winners(&self) -> Vec<usize> {
+        let best = self.players.iter().map(|p| p.score).max().unwrap_or(0);
+        (0..self.players.len())
+            .filter(|&id| self.players[id].score == best)
+            .collect()
+    }
+
+    /// `flip_at` for callers that don't keep time: every set counts as
+    /// found at once.
+    pub fn flip(&mut self, position: usize) -> Vec<GameEvent> {
+        let at = self.since.unwrap_or(0.0);
+        self.flip_at(position, at)
+    }
+
+    /// Turns the card at `position` face up, `at` milliseconds into the
+    /// game. Once a full set is up, or the new card breaks the set, the
+    /// attempt is scored by the `Scoring` rule and the game waits in
+    /// `Phase::Resolving` until `resolve`. Flips that are not allowed
+    /// return no events.
+    pub fn flip_at(&mut self, position: usize, at: f64) -> Vec<GameEvent> {
+        if self.phase != Phase::Playing {
+            return vec![];
+        }
+        match self.cards.get(position) {
+            Some(slot) if slot.face == Face::Down => {}
+            _ => return vec![],
+        }
+
+        self.cards[position].face = Face::Up;
+        self.cards[position].seen = true;
+        self.selected.push(position);
+        self.since.get_or_insert(at);
+        let mut events = vec![GameEvent::Flipped { position }];
+
+        let positions = self.selected.clone();
+        if positions.len() < self.setup.set_size && self.is_match(&positions) {
+            return events;
+        }
+
+        let player = self.turn;
+        let time = self.since.map_or(0.0, |since| at - since);
+        // Only `flip` relies on this, `resolve_at` moves it on.
+        self.since = Some(at);
+
+        self.phase = Phase::Resolving;
+        self.players[player].moves += 1;
+
+        if self.is_full_set(&positions) {
+            self.streak += 1;
+            let bonus = self.setup.scoring.bonus(self.streak, time);
+            let tally = &mut self.players[player];
+            tally.breakdown.sets += 1;
+            tally.breakdown.bonus += bonus;
+            tally.score = tally.breakdown.score();
+            events.push(GameEvent::Matched { player, positions });
+            events.push(GameEvent::Scored {
+                player,
+                score: tally.score,
+            });
+        } else {
+            self.streak = 0;
+            let careless =
+                self.setup.scoring.punishes_known_misses() && positions.iter().any(|&p| self.partner_seen(p));
+            events.push(GameEvent::Mismatched { player, positions });
+            let tally = &mut self.players[player];
+            if careless {
+                tally.breakdown.penalty += 1;
+                tally.score = tally.breakdown.score();
+                events.push(GameEvent::Scored {
+                    player,
+                    score: tally.score,
+                });
+            }
+        }
+
+        events.push(GameEvent::Moved {
+            player,
+            moves: self.players[player].moves,
+        });
+
+        events
+    }
+
+    /// `resolve` `at` milliseconds into the game, which is when the next
+    /// attempt's time starts.
+    pub fn resolve_at(&mut self, at: f64) -> Vec<GameEvent> {
+        let events = self.resolve();
+        if !events.is_empty() {
+            self.since = Some(at);
+        }
+        events
+    }
+
+    /// Settles the cards left face up by `flip`: full sets stay on the board,
+    /// mismatches are turned back down. Whether the turn passes is up to
+    /// the `TurnRule`; it always does after a miss. For callers that don't
+    /// keep time, see `resolve_at`.
+    pub fn resolve(&mut self) -> Vec<GameEvent> {
+        if self.phase != Phase::Resolving {
+            return vec![];
+        }
+
+        let positions: Vec<usize> = self.selected.drain(..).collect();
+        let mut events = vec![];
+        self.phase = Phase::Playing;
+
+        let matched = self.is_full_set(&positions);
+        let face = if matched { Face::Matched } else { Face::Down };
+        for &p in &positions {
+            self.cards[p].face = face;
+        }
+        events.push(if matched {
+            GameEvent::Settled { positions }
+        } else {
+            GameEvent::Hidden { positions }
+        });
+
+        let solo = self.players.len() == 1;
+        let stays = !self.players[self.turn].forfeited;
+        if matched && stays && (solo || self.setup.turns.keeps_turn(true, self.extra)) {
+            self.extra += 1;
+        } else {
+            self.pass_turn(&mut events);
+        }
+
+        if self.cards.iter().all(|c| c.face == Face::Matched) {
+            self.phase = Phase::Over;
+            events.push(GameEvent::Over);
+        }
+
+        events
+    }
+
+    /// Ends the turn without an attempt, when the player's time is up.
+    /// Cards they had turned go back down. Only with company.
+    pub fn pass(&mut self) -> Vec<GameEvent> {
+        if self.phase != Phase::Playing || self.players.len() < 2 {
+            return vec![];
+        }
+
+        let mut events = vec![GameEvent::TimedOut { player: self.turn }];
+        let positions: Vec<usize> = self.selected.drain(..).collect();
+        if !positions.is_empty() {
+            for &p in &positions {
+                self.cards[p].face = Face::Down;
+            }
+            events.push(GameEvent::Hidden { positions });
+        }
+        self.streak = 0;
+        self.since = None;
+        self.pass_turn(&mut events);
+        events
+    }
+
+    /// Takes `player` out of the game, which carries on with the others.
+    /// Cards they had turned go back down. With nobody else left it
+    /// stays their turn, but there is nobody to take it.
+    pub fn forfeit(&mut self, player: usize) -> Vec<GameEvent> {
+        match self.players.get_mut(player) {
+            Some(tally) if !tally.forfeited => tally.forfeited = true,
+            _ => return vec![],
+        }
+        let mut events = vec![];
+        if self.turn != player || self.phase != Phase::Playing {
+            // A set being resolved passes the turn on when it settles.
+            return events;
+        }
+
+        let positions: Vec<usize> = self.selected.drain(..).collect();
+        if !positions.is_empty() {
+            for &p in &positions {
+                self.cards[p].face = Face::Down;
+            }
+            events.push(GameEvent::Hidden { positions });
+        }
+        self.streak = 0;
+        self.since = None;
+        self.pass_turn(&mut events);
+        events
+    }
+
+    /// Hands the turn to the next player still in the game.
+    fn pass_turn(&mut self, events: &mut Vec<GameEvent>) {
+        self.extra = 0;
+        let count = self.players.len();
+        let next = (1..=count)
+            .map(|step| (self.turn + step) % count)
+            .find(|&p| !self.players[p].forfeited)
+            .unwrap_or(self.turn);
+        self.turn = next;
+        events.push(GameEvent::TurnChanged { player: self.turn });
+    }
+
+    /// A copy that is safe to send to players: every face-down card reads
+    /// as value 0, and so does the seed that dealt them until the game is
+    /// over. Only good for showing, not for playing on.
+    pub fn redacted(&self) -> Game {
+        let mut game = self.clone();
+        if game.phase != Phase::Over {
+            game.setup.seed = 0;
+        }
+        for slot in game.cards.iter_mut().filter(|c| c.face == Face::Down) {
+            slot.value = 0;
+        }
+        game
+    }
+
+    pub fn hints_left(&self, player: usize) -> usize {
+        self.setup.hints.saturating_sub(self.players[player].hints)
+    }
+
+    /// Time added to the solo clock for the hints used so far.
+    pub fn penalty_time(&self) -> f64 {
+        if self.players.len() > 1 {
+            return 0.0;
+        }
+        self.players[0].hints as f64 * HINT_TIME_PENALTY
+    }
+
+    /// Points the player in turn to face-down cards: the rest of the set
+    /// being turned up, or else a set whose cards were all seen before, or
+    /// else one card nobody has looked at yet. The cards stay face down;
+    /// showing them is up to the caller. Costs a point with more than one
+    /// player, see `penalty_time` otherwise.
+    pub fn hint(&mut self) -> Vec<GameEvent> {
+        let player = self.turn;
+        if self.phase != Phase::Playing || self.hints_left(player) == 0 {
+            return vec![];
+        }
+
+        let down = |value: usize| -> Vec<usize> {
+            (0..self.cards.len())
+                .filter(|&p| self.cards[p].face == Face::Down && self.cards[p].value == value)
+                .collect()
+        };
+        let positions = match self.selected.first() {
+            Some(&p) => down(self.cards[p].value),
+            None => {
+                let known = self
+                    .cards
+                    .iter()
+                    .filter(|c| c.face == Face::Down)
+                    .map(|c| down(c.value))
+                    .find(|set| set.iter().all(|&p| self.cards[p].seen));
+                match known {
+                    Some(set) => set,
+                    None => (0..self.cards.len())
+                        .find(|&p| self.cards[p].face == Face::Down && !self.cards[p].seen)
+                        .into_iter()
+                        .collect(),
+                }
+            }
+        };
+        if positions.is_empty() {
+            return vec![];
+        }
+
+        let solo = self.players.len() == 1;
+        let tally = &mut self.players[player];
+        tally.hints += 1;
+        let mut events = vec![GameEvent::Hinted {
+            player,
+            positions,
+            hints: tally.hints,
+        }];
+        if !solo {
+            tally.breakdown.penalty += 1;
+            tally.score = tally.breakdown.score();
+            events.push(GameEvent::Scored {
+                player,
+                score: tally.score,
+            });
+        }
+        events
+    }
+
+    /// Whether another card like the one at `position` was seen before and
+    /// is still face down.
+    fn partner_seen(&self, position: usize) -> bool {
+        let value = self.cards[position].value;
+        self.cards
+            .iter()
+            .any(|c| c.value == value && c.face == Face::Down && c.seen)
+    }
+
+    fn is_full_set(&self, positions: &[usize]) -> bool {
+        positions.len() == self.setup.set_size && self.is_match(positions)
+    }
+
+    fn is_match(&self, positions: &[usize]) -> bool {
+        let value = self.cards[positions[0]].value;
+        positions.iter().all(|&p| self.cards[p].value == value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup(cards: usize, players: usize) -> Setup {
+        Setup {
+            cards,
+            players,
+            set_size: 2,
+            seed: 7,
+            hints: 2,
+            scoring: Scoring::Classic,
+            turns: TurnRule::KeepOnMatch,
+        }
+    }
+
+    fn pair_of(game: &Game, value: usize) -> Vec<usize> {
+        game.cards()
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.value == value)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    #[test]
+    fn match_scores_and_keeps_turn() {
+        let mut game = Game::new(setup(16, 2));
+        let pair = pair_of(&game, 3);
+
+        assert_eq!(game.flip(pair[0]), vec![GameEvent::Flipped { position: pair[0] }]);
+        let events = game.flip(pair[1]);
+
+        assert!(events.contains(&GameEvent::Scored { player: 0, score: 1 }));
+        let events = game.resolve();
+        assert!(!events.iter().any(|e| matches!(e, GameEvent::TurnChanged { .. })));
+        assert_eq!(game.turn(), 0);
+        assert_eq!(game.cards()[pair[0]].face, Face::Matched);
+    }
+
+    #[test]
+    fn mismatch_hides_cards_and_passes_turn() {
+        let mut game = Game::new(setup(16, 3));
+        let a = pair_of(&game, 0)[0];
+        let b = pair_of(&game, 1)[0];
+
+        game.flip(a);
+        game.flip(b);
+        let events = game.resolve();
+
+        assert!(events.contains(&GameEvent::TurnChanged { player: 1 }));
+        assert_eq!(game.players()[0].moves, 1);
+        assert_eq!(game.cards()[a].face, Face::Down);
+        assert_eq!(game.cards()[b].face, Face::Down);
+    }
+
+    #[test]
+    fn turn_rules_decide_who_goes_after_a_match() {
+        let find = |game: &mut Game, value: usize| {
+            let pair = pair_of(game, value);
+            game.flip(pair[0]);
+            game.flip(pair[1]);
+            game.resolve();
+            game.turn()
+        };
+
+        let mut game = Game::new(Setup { turns: TurnRule::Alternate, ..setup(16, 2) });
+        assert_eq!(find(&mut game, 0), 1);
+
+        let mut game = Game::new(Setup { turns: TurnRule::Capped(1), ..setup(16, 2) });
+        assert_eq!(find(&mut game, 0), 0);
+        assert_eq!(game.extra_turns(), 1);
+        assert_eq!(find(&mut game, 1), 1);
+        assert_eq!(game.extra_turns(), 0);
+    }
+
+    #[test]
+    fn passing_hides_the_turned_card() {
+        let mut game = Game::new(setup(16, 2));
+        game.flip(4);
+
+        let events = game.pass();
+        assert_eq!(
+            events,
+            vec![
+                GameEvent::TimedOut { player: 0 },
+                GameEvent::Hidden { positions: vec![4] },
+                GameEvent::TurnChanged { player: 1 },
+            ]
+        );
+        assert_eq!(game.cards()[4].face, Face::Down);
+        assert_eq!(game.flip(4), vec![GameEvent::Flipped { position: 4 }]);
+        assert!(Game::new(setup(16, 1)).pass().is_empty());
+    }
+
+    #[test]
+    fn face_up_cards_cannot_be_flipped_again() {
+        let mut game = Game::new(setup(16, 1));
+        game.flip(0);
+
+        assert!(game.flip(0).is_empty());
+        assert!(game.flip(99).is_empty());
+    }
+
+    #[test]
+    fn flips_are_rejected_while_a_pair_is_resolving() {
+        let mut game = Game::new(setup(16, 1));
+        let a = pair_of(&game, 0)[0];
+        let b = pair_of(&game, 1)[0];
+        let c = pair_of(&game, 2)[0];
+
+        game.flip(a);
+        game.flip(b);
+        assert_eq!(game.phase(), Phase::Resolving);
+        assert!(game.flip(c).is_empty());
+
+        game.resolve();
+        assert_eq!(game.phase(), Phase::Playing);
+        assert_eq!(game.flip(c), vec![GameEvent::Flipped { position: c }]);
+    }
+
+    #[test]
+    fn triples_need_three_matching_cards() {
+        let mut game = Game::new(Setup { set_size: 3, ..setup(12, 1) });
+        let triple = pair_of(&game, 2);
+        assert_eq!(triple.len(), 3);
+
+        game.flip(triple[0]);
+        let events = game.flip(triple[1]);
+        assert_eq!(events, vec![GameEvent::Flipped { position: triple[1] }]);
+        assert_eq!(game.phase(), Phase::Playing);
+
+        let events = game.flip(triple[2]);
+        assert!(events.contains(&GameEvent::Scored { player: 0, score: 1 }));
+        game.resolve();
+        assert!(triple.iter().all(|&p| game.cards()[p].face == Face::Matched));
+    }
+
+    #[test]
+    fn a_wrong_card_ends_the_attempt_early() {
+        let mut game = Game::new(Setup { set_size: 4, ..setup(16, 2) });
+        let a = pair_of(&game, 0)[0];
+        let b = pair_of(&game, 1)[0];
+
+        game.flip(a);
+        let events = game.flip(b);
+
+        assert!(events.iter().any(|e| matches!(e, GameEvent::Mismatched { .. })));
+        assert_eq!(game.phase(), Phase::Resolving);
+    }
+
+    #[test]
+    fn streaks_grow_the_points_of_each_set() {
+        let mut game = Game::new(Setup { scoring: Scoring::Streak, ..setup(16, 1) });
+        for value in 0..3 {
+            let pair = pair_of(&game, value);
+            game.flip(pair[0]);
+            game.flip(pair[1]);
+            game.resolve();
+        }
+        assert_eq!(game.players()[0].score, 6);
+
+        let a = pair_of(&game, 3)[0];
+        let b = pair_of(&game, 4)[0];
+        game.flip(a);
+        game.flip(b);
+        game.resolve();
+        let pair = pair_of(&game, 5);
+        game.flip(pair[0]);
+        let events = game.flip(pair[1]);
+        assert!(events.contains(&GameEvent::Scored { player: 0, score: 7 }));
+        assert_eq!(game.players()[0].breakdown, Breakdown { sets: 4, bonus: 3, penalty: 0 });
+    }
+
+    #[test]
+    fn careful_scoring_punishes_missing_a_seen_partner() {
+        let mut game = Game::new(Setup { scoring: Scoring::Careful, ..setup(16, 1) });
+        let a = pair_of(&game, 0);
+        let b = pair_of(&game, 1);
+        let c = pair_of(&game, 2);
+        game.flip(c[0]);
+        game.flip(c[1]);
+        game.resolve();
+
+        // Nothing was known about a yet.
+        game.flip(a[0]);
+        assert_eq!(game.flip(b[0]).len(), 3);
+        game.resolve();
+
+        game.flip(a[1]);
+        let events = game.flip(b[1]);
+        assert!(events.contains(&GameEvent::Scored { player: 0, score: 0 }));
+        assert_eq!(game.players()[0].breakdown.penalty, 1);
+    }
+
+    #[test]
+    fn careful_scoring_checks_every_card_turned() {
+        let mut game = Game::new(Setup { scoring: Scoring::Careful, ..setup(16, 1) });
+        let a = pair_of(&game, 0);
+        let b = pair_of(&game, 1);
+        let c = pair_of(&game, 2);
+        game.flip(a[0]);
+        game.flip(b[0]);
+        game.resolve();
+
+        // The first card is new; it's the second whose partner was seen.
+        game.flip(c[0]);
+        game.flip(b[1]);
+        assert_eq!(game.players()[0].breakdown.penalty, 1);
+        assert_eq!(game.players()[0].score, 0);
+    }
+
+    #[test]
+    fn speed_scoring_rewards_quick_sets() {
+        let mut game = Game::new(Setup { scoring: Scoring::Speed, ..setup(16, 1) });
+        let quick = pair_of(&game, 0);
+        let slow = pair_of(&game, 1);
+
+        game.flip_at(quick[0], 1_000.0);
+        game.flip_at(quick[1], 2_000.0);
+        game.resolve();
+        assert_eq!(game.players()[0].score, 1 + 3);
+
+        game.flip_at(slow[0], 20_000.0);
+        game.flip_at(slow[1], 31_000.0);
+        assert_eq!(game.players()[0].score, 1 + 3 + 1);
+    }
+
+    #[test]
+    fn speed_scoring_ignores_how_long_sets_take_to_resolve() {
+        let play = |delay: f64| {
+            let mut game = Game::new(Setup { scoring: Scoring::Speed, ..setup(16, 1) });
+            let (a, b) = (pair_of(&game, 0), pair_of(&game, 1));
+            game.flip_at(a[0], 0.0);
+            game.flip_at(a[1], 1_000.0);
+            game.resolve_at(1_000.0 + delay);
+            game.flip_at(b[0], 4_000.0 + delay);
+            game.flip_at(b[1], 5_000.0 + delay);
+            game.players()[0].score
+        };
+        assert_eq!(play(400.0), play(5_000.0));
+    }
+
+    #[test]
+    fn speed_scoring_counts_the_time_before_the_first_flip() {
+        let mut game = Game::new(Setup { scoring: Scoring::Speed, ..setup(16, 1) });
+        let (a, b) = (pair_of(&game, 0), pair_of(&game, 1));
+        game.flip_at(a[0], 0.0);
+        game.flip_at(a[1], 1_000.0);
+        game.resolve_at(2_000.0);
+
+        // Six seconds looking before a quick pair leaves one step.
+        game.flip_at(b[0], 8_000.0);
+        game.flip_at(b[1], 8_500.0);
+        assert_eq!(game.players()[0].breakdown.bonus, 3 + 1);
+    }
+
+    #[test]
+    fn hint_points_at_the_rest_of_the_set() {
+        let mut game = Game::new(setup(16, 1));
+        let pair = pair_of(&game, 5);
+        game.flip(pair[0]);
+
+        let events = game.hint();
+        assert_eq!(events, vec![GameEvent::Hinted { player: 0, positions: vec![pair[1]], hints: 1 }]);
+        assert_eq!(game.hints_left(0), 1);
+        assert_eq!(game.penalty_time(), HINT_TIME_PENALTY);
+        assert_eq!(game.cards()[pair[1]].face, Face::Down);
+    }
+
+    #[test]
+    fn hint_prefers_a_set_already_seen() {
+        let mut game = Game::new(setup(16, 2));
+        let a = pair_of(&game, 0);
+        let b = pair_of(&game, 1);
+        game.flip(a[0]);
+        game.flip(b[0]);
+        game.resolve();
+
+        let fresh = game.hint();
+        assert!(matches!(&fresh[0], GameEvent::Hinted { player: 1, positions, .. } if positions.len() == 1));
+
+        game.flip(a[1]);
+        game.flip(b[1]);
+        game.resolve();
+        let known = game.hint();
+        assert!(matches!(&known[0], GameEvent::Hinted { player: 0, positions, .. } if positions == &a || positions == &b));
+    }
+
+    #[test]
+    fn hints_run_out_and_cost_points_with_company() {
+        let mut game = Game::new(setup(16, 2));
+        let pair = pair_of(&game, 0);
+        game.flip(pair[0]);
+        game.flip(pair[1]);
+        game.resolve();
+
+        assert!(game.hint().contains(&GameEvent::Scored { player: 0, score: 0 }));
+        // At 0 points the hint still costs one, paid from the next set.
+        assert!(game.hint().contains(&GameEvent::Scored { player: 0, score: 0 }));
+        assert!(game.hint().is_empty());
+        assert_eq!(game.players()[0].breakdown.penalty, 2);
+        assert_eq!(game.penalty_time(), 0.0);
+
+        let pair = pair_of(&game, 1);
+        game.flip(pair[0]);
+        assert!(game.flip(pair[1]).contains(&GameEvent::Scored { player: 0, score: 0 }));
+    }
+
+    #[test]
+    fn redacted_games_hide_face_down_cards() {
+        let mut game = Game::new(setup(16, 2));
+        let pair = pair_of(&game, 6);
+        game.flip(pair[0]);
+
+        let shown = game.redacted();
+        assert_eq!(shown.cards()[pair[0]].value, 6);
+        assert!(shown.cards().iter().filter(|c| c.face == Face::Down).all(|c| c.value == 0));
+    }
+
+    #[test]
+    fn last_pair_ends_the_game() {
+        let mut game = Game::new(setup(4, 1));
+        let first = pair_of(&game, 0);
+        game.flip(first[0]);
+        game.flip(first[1]);
+        game.resolve();
+        let last = pair_of(&game, 1);
+        game.flip(last[0]);
+        game.flip(last[1]);
+        let events = game.resolve();
+
+        assert_eq!(events.last(), Some(&GameEvent::Over));
+        assert_eq!(game.phase(), Phase::Over);
+        assert!(game.flip(0).is_empty());
+    }
+
+    #[test]
+    fn ranking_orders_by_score_and_reports_ties() {
+        let mut game = Game::new(setup(16, 3));
+        game.players[1].score = 3;
+        game.players[2].score = 5;
+        game.players[0].score = 3;
+
+        assert_eq!(game.ranking(), vec![2, 0, 1]);
+        assert_eq!(game.winners(), vec![2]);
+
+        game.players[0].score = 5;
+        assert_eq!(game.winners(), vec![0, 2]);
+    }
+
+    #[test]
+    fn board_sizes_must_fit_in_pairs() {
+        assert_eq!(check_size(3, 4, 2), Ok(()));
+        assert_eq!(check_size(10, 10, 2), Ok(()));
+        assert_eq!(check_size(3, 3, 2), Err(SizeError::Uneven { rows: 3, columns: 3, set_size: 2 }));
+        assert_eq!(check_size(1, 4, 2), Err(SizeError::OutOfRange { rows: 1, columns: 4 }));
+        assert_eq!(check_size(4, 11, 2), Err(SizeError::OutOfRange { rows: 4, columns: 11 }));
+        assert_eq!(check_size(3, 3, 3), Ok(()));
+        assert_eq!(check_size(4, 4, 3), Err(SizeError::Uneven { rows: 4, columns: 4, set_size: 3 }));
+        assert_eq!(check_size(4, 4, 5), Err(SizeError::SetSize(5)));
+    }
+
+    #[test]
+    fn game_survives_a_json_round_trip() {
+        let mut game = Game::new(setup(16, 2));
+        let pair = pair_of(&game, 5);
+        game.flip(pair[0]);
+        game.flip(pair[1]);
+        game.resolve();
+
+        let json = serde_json::to_string(&game).unwrap();
+        let restored: Game = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.cards()[pair[0]].face, Face::Matched);
+        assert_eq!(restored.players()[0].score, 1);
+        assert_eq!(restored.turn(), game.turn());
+        assert_eq!(restored.phase(), Phase::Playing);
+    }
+
+    #[test]
+    fn deal_is_reproducible_from_seed() {
+        assert_eq!(deal(36, 2, 1234), deal(36, 2, 1234));
+        assert_ne!(deal(36, 2, 1234), deal(36, 2, 4321));
+
+        let mut values = deal(16, 2, 99);
+        values.sort_unstable();
+        let expected: Vec<usize> = (0..8).flat_map(|v| vec![v, v]).collect();
+        assert_eq!(values, expected);
+
+        let mut values = deal(12, 4, 99);
+        values.sort_unstable();
+        let expected: Vec<usize> = (0..3).flat_map(|v| vec![v; 4]).collect();
+        assert_eq!(values, expected);
+    }
+}
@@ -0,0 +1,144 @@
+//! A timestamped record of a game and the board after every step of it.
+//!
+//! Only the events are logged, never the board: the deal comes back from the
+//! seed in `Setup`, and every `Flipped`, `Settled`, `Hidden`, `Hinted` and
+//! `TimedOut` entry is fed to a fresh `Game` again as a `flip`, `resolve`,
+//! `hint` or `pass`.
+
+use serde::{Deserialize, Serialize};
+
+use super::{Game, GameEvent, Setup};
+
+/// A `GameEvent` and the play time, in milliseconds, when it happened.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub at: f64,
+    pub event: GameEvent,
+}
+
+/// The board right after one flip or resolve, and the events it produced.
+#[derive(Clone, Debug)]
+pub struct Frame {
+    pub at: f64,
+    pub game: Game,
+    pub events: Vec<GameEvent>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Replay {
+    frames: Vec<Frame>,
+}
+
+/// Appends `events` to `log`, all stamped with `at`.
+pub fn record(log: &mut Vec<Entry>, at: f64, events: &[GameEvent]) {
+    log.extend(events.iter().map(|event| Entry {
+        at,
+        event: event.clone(),
+    }));
+}
+
+impl Replay {
+    /// Deals `setup` again and plays `log` on it. The first frame is the
+    /// untouched deal; entries the game rejects are skipped.
+    pub fn new(setup: Setup, log: &[Entry]) -> Self {
+        let mut game = Game::new(setup);
+        let mut frames = vec![Frame {
+            at: 0.0,
+            game: game.clone(),
+            events: vec![],
+        }];
+
+        for entry in log {
+            let events = match entry.event {
+                GameEvent::Flipped { position } => game.flip_at(position, entry.at),
+                GameEvent::Settled { .. } | GameEvent::Hidden { .. } => game.resolve_at(entry.at),
+                GameEvent::Hinted { .. } => game.hint(),
+                GameEvent::TimedOut { .. } => game.pass(),
+                _ => continue,
+            };
+            if !events.is_empty() {
+                frames.push(Frame {
+                    at: entry.at,
+                    game: game.clone(),
+                    events,
+                });
+            }
+        }
+
+        Replay { frames }
+    }
+
+    /// Never empty, the deal is always there.
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    pub fn last(&self) -> usize {
+        self.frames.len() - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{Face, Phase};
+
+    fn setup() -> Setup {
+        Setup {
+            cards: 4,
+            players: 2,
+            set_size: 2,
+            seed: 3,
+            hints: 0,
+            scoring: Default::default(),
+            turns: Default::default(),
+        }
+    }
+
+    fn play(game: &mut Game, log: &mut Vec<Entry>, positions: &[usize]) {
+        for &p in positions {
+            let events = game.flip_at(p, p as f64);
+            record(log, p as f64, &events);
+        }
+        let events = game.resolve();
+        record(log, 100.0, &events);
+    }
+
+    #[test]
+    fn replay_ends_on_the_recorded_board() {
+        let mut game = Game::new(setup());
+        let mut log = vec![];
+        let value = game.cards()[0].value;
+        let partner = (1..4).find(|&p| game.cards()[p].value == value).unwrap();
+        let other = (1..4).find(|&p| p != partner).unwrap();
+        let rest: Vec<usize> = (1..4).filter(|&p| p != partner).collect();
+
+        play(&mut game, &mut log, &[0, other]);
+        play(&mut game, &mut log, &[0, partner]);
+        play(&mut game, &mut log, &rest);
+        assert_eq!(game.phase(), Phase::Over);
+
+        let replay = Replay::new(setup(), &log);
+        // Six flips and three resolves after the deal.
+        assert_eq!(replay.frames().len(), 10);
+        assert!(replay.frames()[0].game.cards().iter().all(|c| c.face == Face::Down));
+
+        let end = &replay.frames()[replay.last()].game;
+        assert_eq!(end.phase(), Phase::Over);
+        assert_eq!(end.turn(), game.turn());
+        let scores = |g: &Game| g.players().iter().map(|p| p.score).collect::<Vec<_>>();
+        assert_eq!(scores(end), scores(&game));
+    }
+
+    #[test]
+    fn rejected_entries_are_skipped() {
+        let log = vec![
+            Entry { at: 0.0, event: GameEvent::Flipped { position: 0 } },
+            Entry { at: 1.0, event: GameEvent::Flipped { position: 0 } },
+            Entry { at: 2.0, event: GameEvent::Hidden { positions: vec![0] } },
+            Entry { at: 3.0, event: GameEvent::TurnChanged { player: 1 } },
+        ];
+
+        assert_eq!(Replay::new(setup(), &log).frames().len(), 2);
+    }
+}
@@ -0,0 +1,97 @@
+//! How found sets turn into points.
+//!
+//! Every rule gives a point per set; they differ in the bonus on top and
+//! in what costs a point. The tally of each player keeps the three parts
+//! apart in a `Breakdown` so the results can show where a score came from.
+
+use serde::{Deserialize, Serialize};
+
+/// Under `Scoring::Speed`, sets found within this long of the previous
+/// attempt being settled earn a bonus point for every full `SPEED_STEP`
+/// left. In milliseconds.
+pub const SPEED_WINDOW: f64 = 10_000.0;
+pub const SPEED_STEP: f64 = 2_500.0;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Scoring {
+    /// A point per set, nothing else.
+    #[default]
+    Classic,
+    /// Each set found right after another is worth one more than the last.
+    Streak,
+    /// Missing a card whose partner was already seen costs a point.
+    Careful,
+    /// Sets found quickly after the previous attempt settles earn extra
+    /// points.
+    Speed,
+}
+
+/// Where a player's score came from: `sets + bonus - penalty`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Breakdown {
+    pub sets: u32,
+    pub bonus: u32,
+    /// Points lost to careless misses and to hints.
+    pub penalty: u32,
+}
+
+impl Scoring {
+    pub const ALL: [Scoring; 4] = [Scoring::Classic, Scoring::Streak, Scoring::Careful, Scoring::Speed];
+
+    /// Where its name and description sit in the message catalog.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Scoring::Classic => "classic",
+            Scoring::Streak => "streak",
+            Scoring::Careful => "careful",
+            Scoring::Speed => "speed",
+        }
+    }
+
+    /// Points on top of the set itself. `streak` counts this set and the
+    /// ones found right before it; `time` is how long it took, not counting
+    /// the previous attempt being resolved.
+    pub fn bonus(&self, streak: u32, time: f64) -> u32 {
+        match self {
+            Scoring::Streak => streak.saturating_sub(1),
+            Scoring::Speed => ((SPEED_WINDOW - time).max(0.0) / SPEED_STEP) as u32,
+            Scoring::Classic | Scoring::Careful => 0,
+        }
+    }
+
+    /// Whether a miss costs a point when the partner of one of the cards
+    /// turned had been seen before.
+    pub fn punishes_known_misses(&self) -> bool {
+        *self == Scoring::Careful
+    }
+}
+
+impl Breakdown {
+    /// The score shown for it. Penalties are always kept here but never
+    /// take the score below 0; points found later pay them off first.
+    pub fn score(&self) -> u32 {
+        (self.sets + self.bonus).saturating_sub(self.penalty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bonuses_follow_the_rule() {
+        assert_eq!(Scoring::Classic.bonus(4, 0.0), 0);
+        assert_eq!(Scoring::Streak.bonus(1, 0.0), 0);
+        assert_eq!(Scoring::Streak.bonus(3, 0.0), 2);
+        assert_eq!(Scoring::Speed.bonus(1, 1_000.0), 3);
+        assert_eq!(Scoring::Speed.bonus(1, 9_000.0), 0);
+        assert_eq!(Scoring::Speed.bonus(1, 60_000.0), 0);
+        assert!(Scoring::Careful.punishes_known_misses());
+    }
+
+    #[test]
+    fn penalties_never_take_the_score_below_zero() {
+        assert_eq!(Breakdown { sets: 3, bonus: 2, penalty: 1 }.score(), 4);
+        assert_eq!(Breakdown { sets: 1, bonus: 0, penalty: 2 }.score(), 0);
+    }
+}
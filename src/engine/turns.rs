@@ -0,0 +1,63 @@
+//! Who plays next after an attempt, and how long a turn may last.
+
+use serde::{Deserialize, Serialize};
+
+/// Seconds a player may take per attempt, offered on the setup screen.
+/// `None` is no limit.
+pub const SHOT_CLOCKS: [Option<u32>; 4] = [None, Some(10), Some(20), Some(30)];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TurnRule {
+    /// Finding a set earns another go; a miss passes the turn.
+    #[default]
+    KeepOnMatch,
+    /// The turn passes after every attempt.
+    Alternate,
+    /// Like `KeepOnMatch`, but with at most this many extra goes in a row.
+    Capped(u32),
+}
+
+impl TurnRule {
+    pub const CHOICES: [TurnRule; 4] = [
+        TurnRule::KeepOnMatch,
+        TurnRule::Alternate,
+        TurnRule::Capped(1),
+        TurnRule::Capped(2),
+    ];
+
+    /// Its id in the message catalog; `Capped` fills in its own count.
+    pub fn key(&self) -> &'static str {
+        match self {
+            TurnRule::KeepOnMatch => "keep_on_match",
+            TurnRule::Alternate => "alternate",
+            TurnRule::Capped(_) => "capped",
+        }
+    }
+
+    /// Whether the player in turn goes again after an attempt, having had
+    /// `extra` goes in a row already.
+    pub fn keeps_turn(&self, matched: bool, extra: u32) -> bool {
+        matched
+            && match self {
+                TurnRule::KeepOnMatch => true,
+                TurnRule::Alternate => false,
+                TurnRule::Capped(max) => extra < *max,
+            }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_matches_can_keep_the_turn() {
+        for rule in TurnRule::CHOICES.iter() {
+            assert!(!rule.keeps_turn(false, 0));
+        }
+        assert!(TurnRule::KeepOnMatch.keeps_turn(true, 10));
+        assert!(!TurnRule::Alternate.keeps_turn(true, 0));
+        assert!(TurnRule::Capped(2).keeps_turn(true, 1));
+        assert!(!TurnRule::Capped(2).keeps_turn(true, 2));
+    }
+}
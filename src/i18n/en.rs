@@ -0,0 +1,278 @@
+//! English, also the fallback for anything another bundle leaves out.
+
+pub const MESSAGES: &[(&str, &str)] = &[
+    ("app.title", "memory"),
+    // Setup screen
+    ("setup.theme", "Select Theme"),
+    ("theme.numbers", "Numbers"),
+    ("theme.icons", "Icons"),
+    ("setup.language", "Language"),
+    ("language.auto", "Automatic"),
+    ("setup.animations", "Animations"),
+    ("speed.slow", "Slow"),
+    ("speed.normal", "Normal"),
+    ("speed.fast", "Fast"),
+    ("speed.instant", "Instant"),
+    ("setup.reduced_motion", "Your device asks for reduced motion, so cards change color without moving"),
+    ("setup.sound", "Sound"),
+    ("sound.on", "Sound On"),
+    ("sound.muted", "Muted"),
+    ("sound.volume", "Volume"),
+    ("setup.players", "Number of Players"),
+    ("setup.who", "Who's Playing"),
+    ("profile.name_of", "Name of {name}"),
+    ("profile.color_of", "Color of {name}"),
+    ("profile.who_plays", "Who plays {name}"),
+    ("profile.human", "Human"),
+    ("difficulty.easy", "Easy"),
+    ("difficulty.medium", "Medium"),
+    ("difficulty.hard", "Hard"),
+    ("color.orange", "Orange"),
+    ("color.blue", "Blue"),
+    ("color.green", "Green"),
+    ("color.purple", "Purple"),
+    ("color.pink", "Pink"),
+    ("color.teal", "Teal"),
+    ("setup.turns", "Turns"),
+    ("turns.keep_on_match", "Keep on Match"),
+    ("turns.keep_on_match.about", "Find a set to go again, miss and the turn passes"),
+    ("turns.alternate", "Alternate"),
+    ("turns.alternate.about", "The turn passes after every attempt"),
+    ("turns.capped.one", "{count} Extra Go"),
+    ("turns.capped.other", "{count} Extra Goes"),
+    ("turns.capped.about.one", "Find a set to go again, up to {count} time in a row"),
+    ("turns.capped.about.other", "Find a set to go again, up to {count} times in a row"),
+    ("setup.shot_clock", "Shot clock"),
+    ("shot_clock.none", "No Time Limit"),
+    ("shot_clock.seconds", "{seconds}s per Go"),
+    ("setup.grid", "Grid Size"),
+    ("setup.rows", "Rows"),
+    ("setup.columns", "Columns"),
+    ("setup.match", "Match"),
+    ("setup.hints", "Hints per Game"),
+    ("setup.hints.about", "Each hint adds {time} to a solo game or costs a point against others"),
+    ("setup.scoring", "Scoring"),
+    ("scoring.classic", "Classic"),
+    ("scoring.classic.about", "One point for every set"),
+    ("scoring.streak", "Streaks"),
+    ("scoring.streak.about", "Sets found in a row are worth 1, 2, 3... points"),
+    ("scoring.careful", "Careful"),
+    ("scoring.careful.about", "Missing a card you've already seen the partner of costs a point"),
+    ("scoring.speed", "Speed"),
+    ("scoring.speed.about", "Quick sets earn up to 3 bonus points"),
+    ("setup.seed", "Board Seed"),
+    ("setup.seed.label", "Board seed"),
+    ("setup.random", "Random"),
+    ("setup.daily", "Daily Challenge"),
+    ("daily.done", "Done today in {moves} · {streak}"),
+    ("daily.abandoned", "Given up today after {moves} · {streak}"),
+    ("daily.todo", "{rows}x{columns} pairs, same for everyone · {streak}"),
+    ("daily.streak.one", "{count} day streak"),
+    ("daily.streak.other", "{count} days streak"),
+    ("daily.play", "Play Daily"),
+    ("daily.share.title", "Memory Daily {date}"),
+    ("daily.share.line", "{rows}x{columns} · {moves} · {time}"),
+    ("daily.share.abandoned", "{line} · gave up"),
+    ("setup.online", "Play Online"),
+    ("online.server", "Server address"),
+    ("online.host", "Host Room"),
+    ("online.code", "Code"),
+    ("online.code.label", "Room code"),
+    ("online.join", "Join Room"),
+    ("online.about", "Hosting uses the players and board set above"),
+    ("online.room", "Room {code}"),
+    ("online.online", "Online"),
+    ("online.connecting", "Connecting…"),
+    ("online.reconnecting", "{room} · reconnecting…"),
+    ("online.disconnected", "{room} · disconnected"),
+    ("online.waiting", "{room} · waiting for players ({seated}/{seats})"),
+    ("online.error.players", "Online games need 2 to {max} players, not {players}"),
+    ("online.error.unknown_room", "There is no room {code}"),
+    ("online.error.full", "That room is full"),
+    ("online.error.bad_ticket", "That seat belongs to someone else"),
+    ("online.error.not_seated", "Join a room first"),
+    ("online.error.waiting", "Waiting for everyone to join"),
+    ("online.error.not_your_turn", "It's not your turn"),
+    ("online.error.unreadable", "That message could not be read"),
+    ("online.error.already_seated", "You are already in a room"),
+    ("online.error.bad_address", "{url} is not a server address"),
+    ("online.error.unreachable", "Could not reach the server at {url}"),
+    ("setup.best", "Personal Best"),
+    ("best.record", "{time} · {moves}"),
+    ("best.none", "No record yet"),
+    ("best.reset", "Reset"),
+    ("setup.replay", "Watch a Replay"),
+    ("setup.replay.label", "Replay file"),
+    ("setup.start", "Start Game"),
+    ("setup.resume", "Resume Game"),
+    ("size.out_of_range", "{rows}x{columns} is not allowed, use {min} to {max} rows and columns"),
+    ("size.uneven", "{rows}x{columns} has {cards} cards, which can't be split into {sets}"),
+    ("size.set_size", "Sets of {size} cards are not supported"),
+    ("size.not_numbers", "Rows and columns must be whole numbers"),
+    ("import.unreadable", "The file could not be read"),
+    ("import.malformed", "This is not a memory replay file ({detail})"),
+    ("import.newer", "This replay uses format {version}, but this game only reads up to format {supported}. Try a newer version of the game"),
+    ("import.invalid", "This replay can't be shown: {detail}"),
+    ("import.players", "{players} players are not supported"),
+    ("import.names", "it lists {names} names for {players} players"),
+    // Sets, moves and points
+    ("set.2.one", "pair"),
+    ("set.2.other", "pairs"),
+    ("set.3.one", "triple"),
+    ("set.3.other", "triples"),
+    ("set.4.one", "quad"),
+    ("set.4.other", "quads"),
+    ("set.n.one", "set of {size}"),
+    ("set.n.other", "sets of {size}"),
+    ("moves.one", "{count} move"),
+    ("moves.other", "{count} moves"),
+    ("breakdown.bonus.one", "+ {count} bonus"),
+    ("breakdown.bonus.other", "+ {count} bonus"),
+    ("breakdown.penalty.one", "− {count} penalty"),
+    ("breakdown.penalty.other", "− {count} penalty"),
+    // Game screen
+    ("game.daily", "Daily {date}"),
+    ("game.board", "Board #{seed}"),
+    ("game.practice", "{label} · Practice"),
+    ("game.undo", "Undo"),
+    ("game.redo", "Redo"),
+    ("game.hint", "Hint ({left})"),
+    ("game.restart", "Restart"),
+    ("game.new", "New Game"),
+    ("game.menu", "Menu"),
+    ("stat.time", "Time"),
+    ("stat.moves", "Moves"),
+    ("player.default", "Player{number}"),
+    ("player.away", "{name} (away)"),
+    ("player.open_seat", "Open seat"),
+    ("turn.of", "{name}'s turn"),
+    ("turn.thinking", "{name} is thinking…"),
+    ("turn.extra", "{label} · extra go {extra} of {max}"),
+    ("turn.again", "{label} · going again"),
+    ("turn.seconds", "{label} · {seconds}s"),
+    ("board.live", "Memory board"),
+    ("board.replay", "Replay board"),
+    ("card.down", "Card {number}, face down"),
+    ("card.up", "Card {number}, {face}"),
+    ("card.matched", "Card {number}, {face}, matched"),
+    ("announce.match", "Match! {name} found the {face} {set}"),
+    ("announce.miss", "No match"),
+    ("announce.hint", "Hint: {cards}"),
+    ("announce.hint_card", "card {number} is {face}"),
+    ("announce.timed_out", "Time's up for {name}"),
+    ("announce.over", "Game over"),
+    ("announce.undone", "Move undone"),
+    ("announce.redone", "Move redone"),
+    // Menu
+    ("menu.label", "Menu"),
+    ("menu.paused", "Paused"),
+    ("menu.online", "The game goes on while this menu is open"),
+    ("menu.resume", "Resume Game"),
+    ("menu.mute", "Mute Sounds"),
+    ("menu.unmute", "Unmute Sounds"),
+    ("confirm.restart", "Restart?"),
+    ("confirm.restart.text", "This game will be lost and a new board dealt."),
+    ("confirm.restart.action", "Yes, Restart"),
+    ("confirm.new", "New Game?"),
+    ("confirm.new.text", "This game will be lost."),
+    ("confirm.new.action", "Yes, New Game"),
+    ("confirm.leave", "Leave the Room?"),
+    ("confirm.leave.text", "You will give up your seat in this game."),
+    ("confirm.leave.action", "Yes, Leave"),
+    ("confirm.keep", "Keep Playing"),
+    // Results
+    ("results.solo", "You did it!"),
+    ("results.solo.subtitle", "Game over! Here's how you got on..."),
+    ("results.tie", "It's a tie!"),
+    ("results.wins", "{name} Wins!"),
+    ("results.subtitle", "Game over! Here are the results..."),
+    ("results.best", "New personal best for this setup!"),
+    ("results.practice", "Practice game: moves were undone, so it doesn't count for records"),
+    ("results.download_failed", "The replay could not be downloaded"),
+    ("results.setup", "Setup New Game"),
+    ("results.watch", "Watch Replay"),
+    ("results.download", "Download Replay"),
+    ("results.streak.one", "Daily streak: {count} day"),
+    ("results.streak.other", "Daily streak: {count} days"),
+    ("results.first_only", "Only your first attempt of the day is scored, finished or not"),
+    ("results.share", "Shareable result"),
+    ("results.copy", "Copy Result"),
+    ("results.copied", "Copied!"),
+    ("results.copy_failed", "Copy failed, select the text instead"),
+    ("results.new_best", "{label} (New Best!)"),
+    ("results.time", "Time Elapsed"),
+    ("results.moves", "Moves Taken"),
+    ("results.moves_value.one", "{count} Move"),
+    ("results.moves_value.other", "{count} Moves"),
+    ("results.hints", "Hints Used"),
+    ("results.points", "Points"),
+    ("results.points_value.one", "{count} Point"),
+    ("results.points_value.other", "{count} Points"),
+    ("results.winner", "{name} (Winner!)"),
+    ("results.hints_used.one", "{count} hint"),
+    ("results.hints_used.other", "{count} hints"),
+    // Replay viewer
+    ("replay.title", "replay"),
+    ("replay.step", "Step {step} of {last} · {time}"),
+    ("replay.back_results", "Back to Results"),
+    ("replay.back_setup", "Back to Setup"),
+    ("replay.first", "First step"),
+    ("replay.back", "Step back"),
+    ("replay.play", "Play"),
+    ("replay.pause", "Pause"),
+    ("replay.forward", "Step forward"),
+    ("replay.last", "Last step"),
+    ("replay.speed", "Playback speed"),
+    // Icon theme, read out by screen readers
+    ("icon.circle", "circle"),
+    ("icon.square", "square"),
+    ("icon.triangle", "triangle"),
+    ("icon.diamond", "diamond"),
+    ("icon.star", "star"),
+    ("icon.heart", "heart"),
+    ("icon.plus", "plus"),
+    ("icon.cross", "cross"),
+    ("icon.hexagon", "hexagon"),
+    ("icon.moon", "moon"),
+    ("icon.bolt", "bolt"),
+    ("icon.drop", "drop"),
+    ("icon.ring", "ring"),
+    ("icon.arrow", "arrow"),
+    ("icon.flag", "flag"),
+    ("icon.leaf", "leaf"),
+    ("icon.cloud", "cloud"),
+    ("icon.pentagon", "pentagon"),
+    ("icon.sun", "sun"),
+    ("icon.octagon", "octagon"),
+    ("icon.bars", "bars"),
+    ("icon.frame", "frame"),
+    ("icon.dome", "dome"),
+    ("icon.hourglass", "hourglass"),
+    ("icon.bowtie", "bowtie"),
+    ("icon.chevron", "chevron"),
+    ("icon.house", "house"),
+    ("icon.shield", "shield"),
+    ("icon.egg", "egg"),
+    ("icon.oval", "oval"),
+    ("icon.trapezoid", "trapezoid"),
+    ("icon.parallelogram", "parallelogram"),
+    ("icon.kite", "kite"),
+    ("icon.dots", "dots"),
+    ("icon.checker", "checker"),
+    ("icon.steps", "steps"),
+    ("icon.tee", "tee"),
+    ("icon.corner", "corner"),
+    ("icon.zigzag", "zigzag"),
+    ("icon.bell", "bell"),
+    ("icon.target", "target"),
+    ("icon.window", "window"),
+    ("icon.arch", "arch"),
+    ("icon.wave", "wave"),
+    ("icon.crown", "crown"),
+    ("icon.tag", "tag"),
+    ("icon.pill", "pill"),
+    ("icon.eye", "eye"),
+    ("icon.lock", "lock"),
+    ("icon.hexagram", "hexagram"),
+    ("icon.gem", "gem"),
+];
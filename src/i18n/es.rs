@@ -0,0 +1,278 @@
+//! Spanish.
+
+pub const MESSAGES: &[(&str, &str)] = &[
+    ("app.title", "memoria"),
+    // Setup screen
+    ("setup.theme", "Elige el tema"),
+    ("theme.numbers", "Números"),
+    ("theme.icons", "Iconos"),
+    ("setup.language", "Idioma"),
+    ("language.auto", "Automático"),
+    ("setup.animations", "Animaciones"),
+    ("speed.slow", "Lentas"),
+    ("speed.normal", "Normales"),
+    ("speed.fast", "Rápidas"),
+    ("speed.instant", "Instantáneas"),
+    ("setup.reduced_motion", "Tu dispositivo pide reducir el movimiento, así que las cartas cambian de color sin moverse"),
+    ("setup.sound", "Sonido"),
+    ("sound.on", "Sonido activado"),
+    ("sound.muted", "Silenciado"),
+    ("sound.volume", "Volumen"),
+    ("setup.players", "Número de jugadores"),
+    ("setup.who", "Quién juega"),
+    ("profile.name_of", "Nombre de {name}"),
+    ("profile.color_of", "Color de {name}"),
+    ("profile.who_plays", "Quién juega como {name}"),
+    ("profile.human", "Persona"),
+    ("difficulty.easy", "Fácil"),
+    ("difficulty.medium", "Media"),
+    ("difficulty.hard", "Difícil"),
+    ("color.orange", "Naranja"),
+    ("color.blue", "Azul"),
+    ("color.green", "Verde"),
+    ("color.purple", "Morado"),
+    ("color.pink", "Rosa"),
+    ("color.teal", "Turquesa"),
+    ("setup.turns", "Turnos"),
+    ("turns.keep_on_match", "Sigue al acertar"),
+    ("turns.keep_on_match.about", "Encuentra un grupo para volver a jugar; si fallas, pasa el turno"),
+    ("turns.alternate", "Alternos"),
+    ("turns.alternate.about", "El turno pasa tras cada intento"),
+    ("turns.capped.one", "{count} turno extra"),
+    ("turns.capped.other", "{count} turnos extra"),
+    ("turns.capped.about.one", "Encuentra un grupo para volver a jugar, hasta {count} vez seguida"),
+    ("turns.capped.about.other", "Encuentra un grupo para volver a jugar, hasta {count} veces seguidas"),
+    ("setup.shot_clock", "Tiempo por intento"),
+    ("shot_clock.none", "Sin límite"),
+    ("shot_clock.seconds", "{seconds} s por intento"),
+    ("setup.grid", "Tamaño del tablero"),
+    ("setup.rows", "Filas"),
+    ("setup.columns", "Columnas"),
+    ("setup.match", "Emparejar"),
+    ("setup.hints", "Pistas por partida"),
+    ("setup.hints.about", "Cada pista suma {time} en solitario o cuesta un punto contra otros"),
+    ("setup.scoring", "Puntuación"),
+    ("scoring.classic", "Clásica"),
+    ("scoring.classic.about", "Un punto por cada grupo"),
+    ("scoring.streak", "Rachas"),
+    ("scoring.streak.about", "Los grupos encontrados seguidos valen 1, 2, 3... puntos"),
+    ("scoring.careful", "Atenta"),
+    ("scoring.careful.about", "Fallar una carta cuya pareja ya has visto cuesta un punto"),
+    ("scoring.speed", "Velocidad"),
+    ("scoring.speed.about", "Los grupos rápidos ganan hasta 3 puntos extra"),
+    ("setup.seed", "Semilla del tablero"),
+    ("setup.seed.label", "Semilla del tablero"),
+    ("setup.random", "Aleatoria"),
+    ("setup.daily", "Reto diario"),
+    ("daily.done", "Hecho hoy en {moves} · {streak}"),
+    ("daily.abandoned", "Abandonado hoy tras {moves} · {streak}"),
+    ("daily.todo", "Parejas en {rows}x{columns}, el mismo para todos · {streak}"),
+    ("daily.streak.one", "racha de {count} día"),
+    ("daily.streak.other", "racha de {count} días"),
+    ("daily.play", "Jugar el reto"),
+    ("daily.share.title", "Memoria diaria {date}"),
+    ("daily.share.line", "{rows}x{columns} · {moves} · {time}"),
+    ("daily.share.abandoned", "{line} · abandonado"),
+    ("setup.online", "Jugar en línea"),
+    ("online.server", "Dirección del servidor"),
+    ("online.host", "Crear sala"),
+    ("online.code", "Código"),
+    ("online.code.label", "Código de la sala"),
+    ("online.join", "Unirse"),
+    ("online.about", "La sala usa los jugadores y el tablero elegidos arriba"),
+    ("online.room", "Sala {code}"),
+    ("online.online", "En línea"),
+    ("online.connecting", "Conectando…"),
+    ("online.reconnecting", "{room} · reconectando…"),
+    ("online.disconnected", "{room} · desconectado"),
+    ("online.waiting", "{room} · esperando jugadores ({seated}/{seats})"),
+    ("online.error.players", "Las partidas en línea son de 2 a {max} jugadores, no de {players}"),
+    ("online.error.unknown_room", "No hay ninguna sala {code}"),
+    ("online.error.full", "Esa sala está llena"),
+    ("online.error.bad_ticket", "Ese asiento es de otra persona"),
+    ("online.error.not_seated", "Primero únete a una sala"),
+    ("online.error.waiting", "Esperando a que se unan todos"),
+    ("online.error.not_your_turn", "No es tu turno"),
+    ("online.error.unreadable", "No se pudo leer ese mensaje"),
+    ("online.error.already_seated", "Ya estás en una sala"),
+    ("online.error.bad_address", "{url} no es una dirección de servidor"),
+    ("online.error.unreachable", "No se pudo conectar con el servidor en {url}"),
+    ("setup.best", "Mejor marca"),
+    ("best.record", "{time} · {moves}"),
+    ("best.none", "Aún no hay marca"),
+    ("best.reset", "Borrar"),
+    ("setup.replay", "Ver una repetición"),
+    ("setup.replay.label", "Archivo de repetición"),
+    ("setup.start", "Empezar"),
+    ("setup.resume", "Continuar partida"),
+    ("size.out_of_range", "{rows}x{columns} no está permitido, usa de {min} a {max} filas y columnas"),
+    ("size.uneven", "{rows}x{columns} tiene {cards} cartas, que no se pueden repartir en {sets}"),
+    ("size.set_size", "No se admiten grupos de {size} cartas"),
+    ("size.not_numbers", "Las filas y columnas deben ser números enteros"),
+    ("import.unreadable", "No se pudo leer el archivo"),
+    ("import.malformed", "Este no es un archivo de repetición de memoria ({detail})"),
+    ("import.newer", "Esta repetición usa el formato {version}, pero este juego solo lee hasta el formato {supported}. Prueba una versión más reciente del juego"),
+    ("import.invalid", "No se puede mostrar esta repetición: {detail}"),
+    ("import.players", "no se admiten partidas de {players} jugadores"),
+    ("import.names", "trae {names} nombres para {players} jugadores"),
+    // Sets, moves and points
+    ("set.2.one", "pareja"),
+    ("set.2.other", "parejas"),
+    ("set.3.one", "trío"),
+    ("set.3.other", "tríos"),
+    ("set.4.one", "cuarteto"),
+    ("set.4.other", "cuartetos"),
+    ("set.n.one", "grupo de {size}"),
+    ("set.n.other", "grupos de {size}"),
+    ("moves.one", "{count} movimiento"),
+    ("moves.other", "{count} movimientos"),
+    ("breakdown.bonus.one", "+ {count} de bonificación"),
+    ("breakdown.bonus.other", "+ {count} de bonificación"),
+    ("breakdown.penalty.one", "− {count} de penalización"),
+    ("breakdown.penalty.other", "− {count} de penalización"),
+    // Game screen
+    ("game.daily", "Reto del {date}"),
+    ("game.board", "Tablero n.º {seed}"),
+    ("game.practice", "{label} · Práctica"),
+    ("game.undo", "Deshacer"),
+    ("game.redo", "Rehacer"),
+    ("game.hint", "Pista ({left})"),
+    ("game.restart", "Reiniciar"),
+    ("game.new", "Nueva partida"),
+    ("game.menu", "Menú"),
+    ("stat.time", "Tiempo"),
+    ("stat.moves", "Movimientos"),
+    ("player.default", "Jugador{number}"),
+    ("player.away", "{name} (ausente)"),
+    ("player.open_seat", "Plaza libre"),
+    ("turn.of", "Turno de {name}"),
+    ("turn.thinking", "{name} está pensando…"),
+    ("turn.extra", "{label} · turno extra {extra} de {max}"),
+    ("turn.again", "{label} · vuelve a jugar"),
+    ("turn.seconds", "{label} · {seconds} s"),
+    ("board.live", "Tablero de memoria"),
+    ("board.replay", "Tablero de la repetición"),
+    ("card.down", "Carta {number}, boca abajo"),
+    ("card.up", "Carta {number}, {face}"),
+    ("card.matched", "Carta {number}, {face}, emparejada"),
+    ("announce.match", "¡Acierto! {name} encontró {set} de {face}"),
+    ("announce.miss", "No coinciden"),
+    ("announce.hint", "Pista: {cards}"),
+    ("announce.hint_card", "la carta {number} es {face}"),
+    ("announce.timed_out", "Se acabó el tiempo de {name}"),
+    ("announce.over", "Fin de la partida"),
+    ("announce.undone", "Movimiento deshecho"),
+    ("announce.redone", "Movimiento rehecho"),
+    // Menu
+    ("menu.label", "Menú"),
+    ("menu.paused", "En pausa"),
+    ("menu.online", "La partida sigue mientras este menú está abierto"),
+    ("menu.resume", "Seguir jugando"),
+    ("menu.mute", "Silenciar sonidos"),
+    ("menu.unmute", "Activar sonidos"),
+    ("confirm.restart", "¿Reiniciar?"),
+    ("confirm.restart.text", "Se perderá esta partida y se repartirá un tablero nuevo."),
+    ("confirm.restart.action", "Sí, reiniciar"),
+    ("confirm.new", "¿Nueva partida?"),
+    ("confirm.new.text", "Se perderá esta partida."),
+    ("confirm.new.action", "Sí, nueva partida"),
+    ("confirm.leave", "¿Salir de la sala?"),
+    ("confirm.leave.text", "Dejarás tu plaza en esta partida."),
+    ("confirm.leave.action", "Sí, salir"),
+    ("confirm.keep", "Seguir jugando"),
+    // Results
+    ("results.solo", "¡Lo lograste!"),
+    ("results.solo.subtitle", "¡Fin de la partida! Así te ha ido..."),
+    ("results.tie", "¡Empate!"),
+    ("results.wins", "¡Gana {name}!"),
+    ("results.subtitle", "¡Fin de la partida! Estos son los resultados..."),
+    ("results.best", "¡Nueva mejor marca para esta configuración!"),
+    ("results.practice", "Partida de práctica: se deshicieron movimientos, así que no cuenta para las marcas"),
+    ("results.download_failed", "No se pudo descargar la repetición"),
+    ("results.setup", "Configurar nueva partida"),
+    ("results.watch", "Ver repetición"),
+    ("results.download", "Descargar repetición"),
+    ("results.streak.one", "Racha diaria: {count} día"),
+    ("results.streak.other", "Racha diaria: {count} días"),
+    ("results.first_only", "Solo cuenta tu primer intento del día, lo termines o no"),
+    ("results.share", "Resultado para compartir"),
+    ("results.copy", "Copiar resultado"),
+    ("results.copied", "¡Copiado!"),
+    ("results.copy_failed", "No se pudo copiar, selecciona el texto"),
+    ("results.new_best", "{label} (¡Nueva marca!)"),
+    ("results.time", "Tiempo"),
+    ("results.moves", "Movimientos"),
+    ("results.moves_value.one", "{count} movimiento"),
+    ("results.moves_value.other", "{count} movimientos"),
+    ("results.hints", "Pistas usadas"),
+    ("results.points", "Puntos"),
+    ("results.points_value.one", "{count} punto"),
+    ("results.points_value.other", "{count} puntos"),
+    ("results.winner", "{name} (¡Gana!)"),
+    ("results.hints_used.one", "{count} pista"),
+    ("results.hints_used.other", "{count} pistas"),
+    // Replay viewer
+    ("replay.title", "repetición"),
+    ("replay.step", "Paso {step} de {last} · {time}"),
+    ("replay.back_results", "Volver a los resultados"),
+    ("replay.back_setup", "Volver a la configuración"),
+    ("replay.first", "Primer paso"),
+    ("replay.back", "Paso atrás"),
+    ("replay.play", "Reproducir"),
+    ("replay.pause", "Pausa"),
+    ("replay.forward", "Paso adelante"),
+    ("replay.last", "Último paso"),
+    ("replay.speed", "Velocidad de reproducción"),
+    // Icon theme, read out by screen readers
+    ("icon.circle", "círculo"),
+    ("icon.square", "cuadrado"),
+    ("icon.triangle", "triángulo"),
+    ("icon.diamond", "rombo"),
+    ("icon.star", "estrella"),
+    ("icon.heart", "corazón"),
+    ("icon.plus", "más"),
+    ("icon.cross", "cruz"),
+    ("icon.hexagon", "hexágono"),
+    ("icon.moon", "luna"),
+    ("icon.bolt", "rayo"),
+    ("icon.drop", "gota"),
+    ("icon.ring", "anillo"),
+    ("icon.arrow", "flecha"),
+    ("icon.flag", "bandera"),
+    ("icon.leaf", "hoja"),
+    ("icon.cloud", "nube"),
+    ("icon.pentagon", "pentágono"),
+    ("icon.sun", "sol"),
+    ("icon.octagon", "octágono"),
+    ("icon.bars", "barras"),
+    ("icon.frame", "marco"),
+    ("icon.dome", "cúpula"),
+    ("icon.hourglass", "reloj de arena"),
+    ("icon.bowtie", "pajarita"),
+    ("icon.chevron", "galón"),
+    ("icon.house", "casa"),
+    ("icon.shield", "escudo"),
+    ("icon.egg", "huevo"),
+    ("icon.oval", "óvalo"),
+    ("icon.trapezoid", "trapecio"),
+    ("icon.parallelogram", "paralelogramo"),
+    ("icon.kite", "cometa"),
+    ("icon.dots", "puntos"),
+    ("icon.checker", "damero"),
+    ("icon.steps", "escalera"),
+    ("icon.tee", "te"),
+    ("icon.corner", "esquina"),
+    ("icon.zigzag", "zigzag"),
+    ("icon.bell", "campana"),
+    ("icon.target", "diana"),
+    ("icon.window", "ventana"),
+    ("icon.arch", "arco"),
+    ("icon.wave", "ola"),
+    ("icon.crown", "corona"),
+    ("icon.tag", "etiqueta"),
+    ("icon.pill", "píldora"),
+    ("icon.eye", "ojo"),
+    ("icon.lock", "candado"),
+    ("icon.hexagram", "hexagrama"),
+    ("icon.gem", "gema"),
+];
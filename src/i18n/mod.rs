@@ -0,0 +1,318 @@
+//! Translations of everything the game shows.
+//!
+//! A bundle maps message ids to templates. `{name}` in a template is filled
+//! from the arguments given; messages about a number have one entry per
+//! plural category, `id.one` and `id.other`, picked by the rules of the
+//! language. Anything missing from a bundle is shown in English, and an id
+//! missing from English too is shown as is, so a gap is visible but never
+//! breaks the page.
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+use crate::engine::scoring::Breakdown;
+use crate::engine::turns::TurnRule;
+use crate::engine::{SizeError, MAX_SIDE, MIN_SIDE};
+use crate::online::protocol::OnlineError;
+use crate::online::room::RoomError;
+use crate::profiles::SEATS;
+use crate::replay_file::{ImportError, Invalid, VERSION};
+
+mod en;
+mod es;
+
+type Messages = &'static [(&'static str, &'static str)];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+}
+
+enum Plural {
+    One,
+    Other,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Es];
+
+    /// The language tag, for `<html lang>`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+        }
+    }
+
+    /// The language's own name, so it can be found whatever is on screen.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::Es => "Español",
+        }
+    }
+
+    /// The bundle for a tag like `es-MX`, going by the language alone.
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let language = tag.split(['-', '_']).next()?.to_ascii_lowercase();
+        Locale::ALL.iter().copied().find(|l| l.code() == language)
+    }
+
+    /// The first of `tags`, most preferred first, that has a bundle.
+    pub fn negotiate<'a, I>(tags: I) -> Locale
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        tags.into_iter().find_map(Locale::from_tag).unwrap_or_default()
+    }
+
+    fn messages(&self) -> Messages {
+        match self {
+            Locale::En => en::MESSAGES,
+            Locale::Es => es::MESSAGES,
+        }
+    }
+
+    fn plural(&self, count: usize) -> Plural {
+        match self {
+            Locale::En | Locale::Es if count == 1 => Plural::One,
+            Locale::En | Locale::Es => Plural::Other,
+        }
+    }
+
+    pub fn t(&self, id: &str) -> String {
+        self.t_with(id, &[])
+    }
+
+    pub fn t_with(&self, id: &str, args: &[(&str, &dyn Display)]) -> String {
+        match lookup(self.messages(), id).or_else(|| lookup(en::MESSAGES, id)) {
+            Some(template) => fill(template, args),
+            None => id.to_string(),
+        }
+    }
+
+    /// The `id.one` or `id.other` form for `count`, which also fills
+    /// `{count}`.
+    pub fn t_count(&self, id: &str, count: usize, args: &[(&str, &dyn Display)]) -> String {
+        let form = match self.plural(count) {
+            Plural::One => "one",
+            Plural::Other => "other",
+        };
+        let mut all: Vec<(&str, &dyn Display)> = vec![("count", &count)];
+        all.extend_from_slice(args);
+        self.t_with(&format!("{}.{}", id, form), &all)
+    }
+
+    /// "pair", "triples", "sets of 5"... for `count` sets of `set_size`
+    /// cards.
+    pub fn set_name(&self, set_size: usize, count: usize) -> String {
+        match set_size {
+            2..=4 => self.t_count(&format!("set.{}", set_size), count, &[]),
+            _ => self.t_count("set.n", count, &[("size", &set_size)]),
+        }
+    }
+
+    /// "5 pairs + 3 bonus − 1 penalty", leaving out the parts that are 0.
+    pub fn breakdown(&self, breakdown: &Breakdown, set_size: usize) -> String {
+        let sets = breakdown.sets as usize;
+        let mut text = format!("{} {}", sets, self.set_name(set_size, sets));
+        if breakdown.bonus > 0 {
+            text.push(' ');
+            text.push_str(&self.t_count("breakdown.bonus", breakdown.bonus as usize, &[]));
+        }
+        if breakdown.penalty > 0 {
+            text.push(' ');
+            text.push_str(&self.t_count("breakdown.penalty", breakdown.penalty as usize, &[]));
+        }
+        text
+    }
+
+    pub fn turn_rule(&self, rule: TurnRule) -> String {
+        match rule {
+            TurnRule::Capped(max) => self.t_count("turns.capped", max as usize, &[]),
+            _ => self.t(&format!("turns.{}", rule.key())),
+        }
+    }
+
+    pub fn turn_rule_about(&self, rule: TurnRule) -> String {
+        match rule {
+            TurnRule::Capped(max) => self.t_count("turns.capped.about", max as usize, &[]),
+            _ => self.t(&format!("turns.{}.about", rule.key())),
+        }
+    }
+
+    pub fn size_error(&self, error: &SizeError) -> String {
+        match *error {
+            SizeError::OutOfRange { rows, columns } => self.t_with(
+                "size.out_of_range",
+                &[("rows", &rows), ("columns", &columns), ("min", &MIN_SIDE), ("max", &MAX_SIDE)],
+            ),
+            SizeError::Uneven { rows, columns, set_size } => self.t_with(
+                "size.uneven",
+                &[
+                    ("rows", &rows),
+                    ("columns", &columns),
+                    ("cards", &(rows * columns)),
+                    ("sets", &self.set_name(set_size, 2)),
+                ],
+            ),
+            SizeError::SetSize(size) => self.t_with("size.set_size", &[("size", &size)]),
+        }
+    }
+
+    pub fn online_error(&self, error: &OnlineError) -> String {
+        match error {
+            OnlineError::Room(RoomError::Size(error)) => self.size_error(error),
+            OnlineError::Room(RoomError::Players(players)) => {
+                self.t_with("online.error.players", &[("players", players), ("max", &SEATS)])
+            }
+            OnlineError::Room(RoomError::UnknownRoom(code)) => {
+                self.t_with("online.error.unknown_room", &[("code", code)])
+            }
+            OnlineError::Room(RoomError::Full) => self.t("online.error.full"),
+            OnlineError::Room(RoomError::BadTicket) => self.t("online.error.bad_ticket"),
+            OnlineError::Room(RoomError::NotSeated) => self.t("online.error.not_seated"),
+            OnlineError::Room(RoomError::Waiting) => self.t("online.error.waiting"),
+            OnlineError::Room(RoomError::NotYourTurn) => self.t("online.error.not_your_turn"),
+            OnlineError::Unreadable => self.t("online.error.unreadable"),
+            OnlineError::AlreadySeated => self.t("online.error.already_seated"),
+            OnlineError::BadAddress(url) => self.t_with("online.error.bad_address", &[("url", url)]),
+            OnlineError::Unreachable(url) => self.t_with("online.error.unreachable", &[("url", url)]),
+        }
+    }
+
+    pub fn import_error(&self, error: &ImportError) -> String {
+        match error {
+            ImportError::Unreadable => self.t("import.unreadable"),
+            ImportError::Malformed(detail) => self.t_with("import.malformed", &[("detail", detail)]),
+            ImportError::NewerVersion(version) => {
+                self.t_with("import.newer", &[("version", version), ("supported", &VERSION)])
+            }
+            ImportError::Invalid(invalid) => {
+                let detail = match *invalid {
+                    Invalid::Size(ref error) => self.size_error(error),
+                    Invalid::Players(players) => self.t_with("import.players", &[("players", &players)]),
+                    Invalid::Names { names, players } => {
+                        self.t_with("import.names", &[("names", &names), ("players", &players)])
+                    }
+                };
+                self.t_with("import.invalid", &[("detail", &detail)])
+            }
+        }
+    }
+}
+
+fn lookup(messages: Messages, id: &str) -> Option<&'static str> {
+    messages.iter().find(|(key, _)| *key == id).map(|(_, template)| *template)
+}
+
+/// Replaces every `{name}` in `template` with its argument. Names without
+/// one are left as they are.
+fn fill(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let Some(close) = rest[open..].find('}').map(|c| open + c) else {
+            break;
+        };
+        text.push_str(&rest[..open]);
+        let name = &rest[open + 1..close];
+        match args.iter().find(|(arg, _)| *arg == name) {
+            Some((_, value)) => text.push_str(&value.to_string()),
+            None => text.push_str(&rest[open..=close]),
+        }
+        rest = &rest[close + 1..];
+    }
+    text.push_str(rest);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn placeholders(template: &str) -> BTreeSet<&str> {
+        template
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split('}').next())
+            .collect()
+    }
+
+    #[test]
+    fn templates_fill_their_placeholders() {
+        assert_eq!(fill("{name}'s turn", &[("name", &"Ada")]), "Ada's turn");
+        assert_eq!(fill("{a} and {b}", &[("b", &2)]), "{a} and 2");
+        assert_eq!(fill("open { brace", &[]), "open { brace");
+    }
+
+    #[test]
+    fn plurals_follow_the_count() {
+        assert_eq!(Locale::En.t_count("moves", 1, &[]), "1 move");
+        assert_eq!(Locale::En.t_count("moves", 0, &[]), "0 moves");
+        assert_eq!(Locale::Es.t_count("moves", 3, &[]), "3 movimientos");
+        assert_eq!(Locale::Es.set_name(3, 1), "trío");
+        assert_eq!(Locale::En.set_name(5, 2), "sets of 5");
+    }
+
+    #[test]
+    fn missing_messages_fall_back() {
+        assert_eq!(Locale::Es.t("no.such.message"), "no.such.message");
+        assert_eq!(lookup(es::MESSAGES, "app.title"), Some("memoria"));
+    }
+
+    #[test]
+    fn browser_languages_pick_a_bundle() {
+        assert_eq!(Locale::negotiate(["fr-FR", "es-MX", "en"]), Locale::Es);
+        assert_eq!(Locale::negotiate(["de"]), Locale::En);
+        assert_eq!(Locale::from_tag("EN_gb"), Some(Locale::En));
+    }
+
+    #[test]
+    fn breakdown_describes_its_parts() {
+        let breakdown = Breakdown { sets: 5, bonus: 3, penalty: 1 };
+        assert_eq!(Locale::En.breakdown(&breakdown, 2), "5 pairs + 3 bonus − 1 penalty");
+        assert_eq!(Locale::En.breakdown(&Breakdown { sets: 1, ..Default::default() }, 3), "1 triple");
+        assert_eq!(Locale::Es.breakdown(&breakdown, 2), "5 parejas + 3 de bonificación − 1 de penalización");
+    }
+
+    #[test]
+    fn import_errors_are_told_in_one_language() {
+        let names = ImportError::Invalid(Invalid::Names { names: 3, players: 2 });
+        assert_eq!(
+            Locale::Es.import_error(&names),
+            "No se puede mostrar esta repetición: trae 3 nombres para 2 jugadores"
+        );
+        let size = ImportError::Invalid(Invalid::Size(SizeError::SetSize(7)));
+        assert_eq!(
+            Locale::Es.import_error(&size),
+            "No se puede mostrar esta repetición: No se admiten grupos de 7 cartas"
+        );
+    }
+
+    #[test]
+    fn online_errors_are_told_in_the_players_language() {
+        let full = OnlineError::Room(RoomError::Full);
+        assert_eq!(Locale::En.online_error(&full), "That room is full");
+        assert_eq!(Locale::Es.online_error(&full), "Esa sala está llena");
+        let players = OnlineError::Room(RoomError::Players(1));
+        assert_eq!(Locale::Es.online_error(&players), "Las partidas en línea son de 2 a 4 jugadores, no de 1");
+    }
+
+    #[test]
+    fn bundles_translate_the_same_messages() {
+        let ids = |messages: Messages| messages.iter().map(|(id, _)| *id).collect::<BTreeSet<_>>();
+        for locale in Locale::ALL {
+            let messages = locale.messages();
+            assert_eq!(ids(messages).len(), messages.len(), "{:?} repeats an id", locale);
+            assert_eq!(ids(messages), ids(en::MESSAGES), "{:?} differs from English", locale);
+            for (id, template) in messages {
+                let english = lookup(en::MESSAGES, id).unwrap();
+                assert_eq!(placeholders(template), placeholders(english), "{:?} {}", locale, id);
+            }
+        }
+    }
+}
@@ -1,12 +1,11 @@
-use core::cmp::Ordering;
-use dominator::{clone, events, html, Dom};
-use futures::future::ready;
+use dominator::{apply_methods, clone, events, html, traits::StaticEvent, Dom, EventOptions};
 use futures_signals::{
-    signal::{Mutable, Signal, SignalExt},
+    map_ref,
+    signal::{self, Mutable, Signal, SignalExt},
     signal_vec::{MutableVec, SignalVecExt},
 };
 use rand::{thread_rng, Rng};
-use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
 use std::default::Default;
 use std::sync::Arc;
 use wasm_bindgen::prelude::*;
@@ -15,6 +14,41 @@ use wasm_bindgen_futures::spawn_local;
 
 mod components;
 mod containers;
+mod daily;
+pub mod engine;
+mod files;
+mod i18n;
+pub mod online;
+pub mod profiles;
+mod records;
+mod replay_file;
+mod save;
+mod settings;
+mod sound;
+mod storage;
+pub mod timing;
+
+use components::icons::{IconSet, SETS};
+use daily::{Daily, DailyResult};
+use engine::bot::{Bot, Difficulty};
+use engine::clock::Clock;
+use engine::{Game, GameEvent, Phase, Setup, SizeError};
+use engine::grid::{self, Direction};
+use engine::history::History;
+use engine::replay::{self, Entry};
+use engine::scoring::Scoring;
+use engine::turns::TurnRule;
+use engine::Face;
+use i18n::Locale;
+use online::client::{Connection, Status};
+use online::protocol::{ClientMessage, OnlineError, SeatView, ServerMessage};
+use profiles::{Color, Profile};
+use records::{Improvement, Records};
+use replay_file::ReplayFile;
+use save::SavedGame;
+use settings::Settings;
+use sound::Sound;
+use timing::Timing;
 
 #[derive(Clone, Debug, Copy, PartialEq, Eq)]
 pub enum CardState {
@@ -23,6 +57,7 @@ pub enum CardState {
     Wrong,
     Fine,
     Selected,
+    Hinted,
 }
 
 #[derive(Clone, Debug, Copy, PartialEq, Eq)]
@@ -36,34 +71,84 @@ pub enum GameStates {
     Initial,
     Playing,
     Over,
+    Replay,
+}
+
+/// The menu over the game screen. While it is open the board is covered
+/// and, offline, the game stands still.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum Menu {
+    Closed,
+    Open,
+    /// Asking before throwing away the game in progress.
+    Confirm(Discard),
+}
+
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum Discard {
+    Restart,
+    NewGame,
+}
+
+/// What undo brings back: the board, the move log and the clock, paused.
+#[derive(Clone, Debug)]
+struct Snapshot {
+    game: Game,
+    log: Vec<Entry>,
+    clock: Clock,
 }
 
-#[derive(Clone, Debug, Copy, PartialEq)]
+#[derive(Clone, Debug, Copy, PartialEq, Serialize, Deserialize)]
 pub enum GameTheme {
     Numbers,
     Icons,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Config {
     pub theme: GameTheme,
     pub players: usize,
-    pub size: usize,
+    pub rows: usize,
+    pub columns: usize,
+    pub set_size: usize,
+    pub seed: u64,
+    /// The day of a daily challenge; `None` for boards set up by hand.
+    #[serde(default)]
+    pub daily: Option<i64>,
+    /// Hints each player may use per game.
+    #[serde(default)]
+    pub hints: usize,
+    /// Seats played by the computer, and how well.
+    #[serde(default)]
+    pub computers: [Option<Difficulty>; profiles::SEATS],
+    #[serde(default)]
+    pub scoring: Scoring,
+    #[serde(default)]
+    pub turns: TurnRule,
+    /// Seconds per attempt before the turn passes; `None` for no limit.
+    #[serde(default)]
+    pub shot_clock: Option<u32>,
 }
 
 #[derive(Debug)]
 pub struct Card {
-    id: u8,
-    value: usize,
+    id: usize,
+    /// Online, face-down cards of other players' boards read as 0 until
+    /// the server shows them.
+    value: Mutable<usize>,
     state: Mutable<CardState>,
 }
 
 #[derive(Clone, Debug)]
 pub struct Player {
     id: usize,
+    name: Mutable<String>,
+    color: Mutable<Color>,
+    computer: Option<Difficulty>,
     score: Mutable<u32>,
     state: Mutable<PlayerState>,
     moves: Mutable<usize>,
+    hints: Mutable<usize>,
     time: Option<Mutable<f64>>,
     points: Mutable<bool>,
 }
@@ -75,6 +160,76 @@ pub struct App {
     players: MutableVec<Arc<Player>>,
     cards: MutableVec<Arc<Card>>,
     player_in_turn: Mutable<usize>,
+    game: Mutable<Game>,
+    clock: Mutable<Clock>,
+    /// Time used in the current attempt, for the shot clock.
+    shot: Mutable<Clock>,
+    shot_left: Mutable<Option<u32>>,
+    hidden: Mutable<bool>,
+    /// The device asks for as little motion as possible.
+    reduced_motion: Mutable<bool>,
+    /// The first of the browser's languages there is a bundle for.
+    browser_locale: Mutable<Locale>,
+    settings: Mutable<Settings>,
+    menu: Mutable<Menu>,
+    records: Mutable<Records>,
+    improvement: Mutable<Improvement>,
+    saved: Mutable<Option<SavedGame>>,
+    focus: Mutable<usize>,
+    announcement: Mutable<String>,
+    log: Mutable<Vec<Entry>>,
+    history: Mutable<History<Snapshot>>,
+    /// Moves were undone in this game, so it doesn't count for records.
+    practice: Mutable<bool>,
+    replay: Mutable<Option<Arc<ReplayFile>>>,
+    daily: Mutable<Daily>,
+    /// The day whose scored attempt is on the board, if it is.
+    daily_attempt: Mutable<Option<i64>>,
+    daily_scored: Mutable<bool>,
+    profiles: Mutable<Vec<Profile>>,
+    bots: Mutable<Vec<Option<Bot>>>,
+    online: Mutable<Option<Arc<Connection>>>,
+    seats: Mutable<Vec<Option<SeatView>>>,
+    online_error: Mutable<Option<OnlineError>>,
+}
+
+impl CardState {
+    /// The resting look of a card whose face is `face`.
+    fn from_face(face: Face) -> Self {
+        match face {
+            Face::Down => CardState::Hidden,
+            Face::Up => CardState::Selected,
+            Face::Matched => CardState::Shown,
+        }
+    }
+}
+
+impl GameTheme {
+    pub fn icon_set(&self) -> Option<&'static IconSet> {
+        match self {
+            GameTheme::Numbers => None,
+            GameTheme::Icons => SETS.first(),
+        }
+    }
+}
+
+impl Config {
+    /// Number of cards on the board.
+    pub fn size(&self) -> usize {
+        self.rows * self.columns
+    }
+
+    pub fn setup(&self) -> Setup {
+        Setup {
+            cards: self.size(),
+            players: self.players,
+            set_size: self.set_size,
+            seed: self.seed,
+            hints: self.hints,
+            scoring: self.scoring,
+            turns: self.turns,
+        }
+    }
 }
 
 impl Default for Config {
@@ -82,29 +237,106 @@ impl Default for Config {
         Config {
             theme: GameTheme::Numbers,
             players: 1,
-            size: 16,
+            rows: 4,
+            columns: 4,
+            set_size: 2,
+            seed: random_seed(),
+            daily: None,
+            hints: 3,
+            computers: [None; profiles::SEATS],
+            scoring: Scoring::Classic,
+            turns: TurnRule::KeepOnMatch,
+            shot_clock: None,
         }
     }
 }
 
+/// How long a hinted card stays visible, in milliseconds.
+const HINT_REVEAL: u32 = 1500;
+
+/// How often a paused timer checks whether the game is back on, in
+/// milliseconds.
+const PAUSE_POLL: u32 = 50;
+
+/// Seeds are kept short so they are easy to read out and type back in.
+pub fn random_seed() -> u64 {
+    thread_rng().gen_range(0..1_000_000)
+}
+
+pub fn now() -> f64 {
+    js_sys::Date::now()
+}
+
+/// Today's day number in the local calendar, see `daily`.
+pub fn today() -> i64 {
+    let date = js_sys::Date::new_0();
+    daily::days_from_civil(date.get_full_year() as i64, date.get_month() + 1, date.get_date())
+}
+
+pub fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Whether the device asks for reduced motion.
+fn prefers_reduced_motion() -> bool {
+    web_sys::window()
+        .and_then(|w| w.match_media("(prefers-reduced-motion: reduce)").ok().flatten())
+        .is_some_and(|query| query.matches())
+}
+
+/// The browser's preferred languages, matched against the bundles.
+fn browser_locale() -> Locale {
+    let navigator = match web_sys::window() {
+        Some(window) => window.navigator(),
+        None => return Locale::default(),
+    };
+    let mut tags: Vec<String> = navigator.languages().iter().filter_map(|tag| tag.as_string()).collect();
+    tags.extend(navigator.language());
+    Locale::negotiate(tags.iter().map(String::as_str))
+}
+
+/// Formats a duration in milliseconds as `m:ss`.
+pub fn format_time(ms: f64) -> String {
+    let secs = (ms / 1000.0).floor() as u64;
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
 impl Card {
-    fn new(value: usize, id: u8) -> Self {
+    fn new(value: usize, id: usize) -> Self {
         Card {
             id,
-            value,
+            value: Mutable::new(value),
             state: Mutable::new(CardState::Hidden),
         }
     }
+
+    /// Shows how a pick turned out, unless the card has already moved on:
+    /// in a background tab the reveal can be late enough that the server
+    /// has settled or hidden it first.
+    fn settle(&self, state: CardState) {
+        let mut current = self.state.lock_mut();
+        if *current == CardState::Selected {
+            *current = state;
+        }
+    }
 }
 
 impl Player {
-    fn new(id: usize) -> Self {
+    fn new(id: usize, timed: bool, name: String, profile: &Profile, computer: Option<Difficulty>) -> Self {
         Player {
             id,
+            name: Mutable::new(name),
+            color: Mutable::new(profile.color),
+            computer,
             score: Mutable::new(0u32),
             state: Mutable::new(PlayerState::Iddle),
             moves: Mutable::new(0),
-            time: None,
+            hints: Mutable::new(0),
+            time: if timed { Some(Mutable::new(0.0)) } else { None },
             points: Mutable::new(false),
         }
     }
@@ -113,50 +345,244 @@ impl Player {
 impl App {
     async fn new() -> Arc<Self> {
         let cfg = Config::default();
-        let cards = MutableVec::new();
-        let players = MutableVec::new();
-
-        for i in 0..cfg.size {
-            let ind = i % (cfg.size / 2);
-            cards
-                .lock_mut()
-                .push_cloned(Arc::new(Card::new(ind, i as u8)));
-        }
-
-        players.lock_mut().push_cloned(Arc::new(Player::new(0)));
-
+        let game = Game::new(cfg.setup());
 
-        Arc::new(Self {
+        let app = Arc::new(Self {
             state: Mutable::new(GameStates::Initial),
             config: Mutable::new(cfg),
-            players,
-            cards,
+            players: MutableVec::new(),
+            cards: MutableVec::new(),
             player_in_turn: Mutable::new(0),
-        })
+            game: Mutable::new(game),
+            clock: Mutable::new(Clock::default()),
+            shot: Mutable::new(Clock::default()),
+            shot_left: Mutable::new(None),
+            hidden: Mutable::new(false),
+            reduced_motion: Mutable::new(prefers_reduced_motion()),
+            browser_locale: Mutable::new(browser_locale()),
+            settings: Mutable::new(storage::load(settings::STORAGE_KEY).unwrap_or_default()),
+            menu: Mutable::new(Menu::Closed),
+            records: Mutable::new(storage::load(records::STORAGE_KEY).unwrap_or_default()),
+            improvement: Mutable::new(Improvement::default()),
+            saved: Mutable::new(storage::load(save::STORAGE_KEY)),
+            focus: Mutable::new(0),
+            announcement: Mutable::new(String::new()),
+            log: Mutable::new(vec![]),
+            history: Mutable::new(History::default()),
+            practice: Mutable::new(false),
+            replay: Mutable::new(None),
+            daily: Mutable::new(storage::load(daily::STORAGE_KEY).unwrap_or_default()),
+            daily_attempt: Mutable::new(None),
+            daily_scored: Mutable::new(false),
+            profiles: Mutable::new(profiles::complete(storage::load(profiles::STORAGE_KEY).unwrap_or_default())),
+            bots: Mutable::new(vec![]),
+            online: Mutable::new(None),
+            seats: Mutable::new(vec![]),
+            online_error: Mutable::new(None),
+        });
+
+        App::restart(app.clone());
+        app
     }
 
     pub fn restart(app: Arc<Self>) {
-        let cfg = app.config.lock_ref();
-        let mut cards = vec![];
-        let mut players = vec![];
-        
-        for i in 0..cfg.size {
-            let ind = i % (cfg.size / 2);
-            cards
-                .push(Arc::new(Card::new(ind, i as u8)));
-        }
-
-        
-       for i in 0..cfg.players{
-            players.push(Arc::new(Player::new(i)));
-        } 
- 
-        app.cards.lock_mut().clear();
-        // let mut r = thread_rng();
-        // let sh = cards.shuffle(&mut r); 
+        // Dealing again gives up the day's attempt as it stands.
+        if let Some(day) = app.daily_attempt.replace(None) {
+            app.save_daily(day, true);
+        }
+        let cfg = app.config.get_cloned();
+        App::load_game(app.clone(), Game::new(cfg.setup()));
+        app.log.set(vec![]);
+        app.history.set(History::default());
+        app.practice.set(false);
+        app.clock.set(Clock::default());
+        app.improvement.set(Improvement::default());
+        app.daily_scored.set(false);
+    }
+
+    /// Rebuilds cards and players from the state held by `game`.
+    fn load_game(app: Arc<Self>, game: Game) {
+        let solo = app.config.lock_ref().players == 1;
+        let computers = app.config.lock_ref().computers;
+        let profiles = app.profiles.get_cloned();
+
+        let cards: Vec<_> = game
+            .cards()
+            .iter()
+            .enumerate()
+            .map(|(i, slot)| {
+                let card = Card::new(slot.value, i);
+                card.state.set(CardState::from_face(slot.face));
+                Arc::new(card)
+            })
+            .collect();
+        let players: Vec<_> = game
+            .players()
+            .iter()
+            .enumerate()
+            .map(|(i, tally)| {
+                let player = Player::new(i, solo, app.seat_name(&profiles[i], i), &profiles[i], computers[i]);
+                player.score.set(tally.score);
+                player.moves.set(tally.moves);
+                player.hints.set(tally.hints);
+                Arc::new(player)
+            })
+            .collect();
+
+        app.bots.set(players.iter().map(|p| p.computer.map(Bot::new)).collect());
+        app.shot.set(Clock::default());
+        app.shot_left.set(None);
+        app.player_in_turn.set(game.turn());
+        app.menu.set(Menu::Closed);
+        app.focus.set(0);
+        app.announcement.set(String::new());
+        app.game.set(game);
         app.cards.lock_mut().replace_cloned(cards);
         app.players.lock_mut().replace_cloned(players);
- 
+    }
+
+    /// Continues the game found in `localStorage` when the page was loaded.
+    pub fn resume(app: Arc<Self>) {
+        let saved = match app.saved.replace(None) {
+            Some(saved) => saved,
+            None => return,
+        };
+
+        app.daily_attempt.set(saved.config.daily.filter(|_| saved.daily_attempt));
+        app.config.set(saved.config);
+        App::load_game(app.clone(), saved.game);
+        app.log.set(saved.log);
+        app.history.set(History::default());
+        app.practice.set(saved.practice);
+        app.clock.set(Clock::from_elapsed(saved.elapsed));
+        app.improvement.set(Improvement::default());
+        app.state.set(GameStates::Playing);
+        app.start_turn();
+        app.sync_clock();
+        app.tick();
+        App::schedule_bot(app);
+    }
+
+    fn autosave(&self) {
+        // Online, the server keeps the game.
+        if self.online.lock_ref().is_some() {
+            return;
+        }
+
+        // A pair still animating is saved as already settled.
+        let elapsed = self.clock.get().elapsed(now());
+        let mut game = self.game.get_cloned();
+        let events = game.resolve_at(elapsed);
+        if game.phase() == Phase::Over || !self.clock.get().is_started() {
+            return;
+        }
+
+        let mut log = self.log.get_cloned();
+        replay::record(&mut log, elapsed, &events);
+
+        storage::save(save::STORAGE_KEY, &SavedGame {
+            config: self.config.get_cloned(),
+            game,
+            elapsed,
+            log,
+            practice: self.practice.get(),
+            daily_attempt: self.daily_attempt.get().is_some(),
+        });
+        if let Some(day) = self.daily_attempt.get() {
+            self.save_daily(day, true);
+        }
+    }
+
+    /// Deals a fresh board with the same settings and keeps playing.
+    pub fn play_again(app: Arc<Self>) {
+        App::discard_saved(app.clone());
+        App::change_seed(app.clone(), random_seed());
+        app.state.set(GameStates::Playing);
+        app.start_turn();
+        App::schedule_bot(app);
+    }
+
+    /// Deals a fresh board and goes back to the setup screen.
+    pub fn setup_new_game(app: Arc<Self>) {
+        app.leave_online();
+        App::discard_saved(app.clone());
+        App::change_seed(app.clone(), random_seed());
+        app.state.set(GameStates::Initial);
+    }
+
+    pub fn player_names(&self) -> Vec<String> {
+        self.players.lock_ref().iter().map(|p| p.name.get_cloned()).collect()
+    }
+
+    fn player_name(&self, id: usize) -> String {
+        self.players.lock_ref()[id].name.get_cloned()
+    }
+
+    /// `Profile::display_name` in the player's language.
+    fn seat_name(&self, profile: &Profile, seat: usize) -> String {
+        profile.display_name(self.locale(), seat)
+    }
+
+    /// Names the players again, for when the language changes.
+    fn rename_players(&self) {
+        if self.online.lock_ref().is_some() {
+            self.name_seats();
+            return;
+        }
+        let profiles = self.profiles.lock_ref();
+        for (seat, player) in self.players.lock_ref().iter().enumerate() {
+            player.name.set(self.seat_name(&profiles[seat], seat));
+        }
+    }
+
+    /// Edits the stored profile of `seat` and the player sitting there.
+    pub fn change_profile<F>(app: Arc<Self>, seat: usize, f: F)
+    where
+        F: FnOnce(&mut Profile),
+    {
+        let mut profiles = app.profiles.lock_mut();
+        f(&mut profiles[seat]);
+        storage::save(profiles::STORAGE_KEY, &*profiles);
+
+        if let Some(player) = app.players.lock_ref().get(seat) {
+            player.name.set(app.seat_name(&profiles[seat], seat));
+            player.color.set(profiles[seat].color);
+        }
+    }
+
+    /// The game on the board, as it would be shared.
+    fn replay_file(&self) -> ReplayFile {
+        ReplayFile::new(self.config.get_cloned(), self.player_names(), self.log.get_cloned())
+    }
+
+    /// Opens the replay of the game that just ended.
+    pub fn watch_replay(app: Arc<Self>) {
+        let file = app.replay_file();
+        App::load_replay(app, file);
+    }
+
+    /// Opens a replay loaded from a file.
+    pub fn load_replay(app: Arc<Self>, file: ReplayFile) {
+        app.replay.set(Some(Arc::new(file)));
+        app.state.set(GameStates::Replay);
+    }
+
+    /// Goes back to the results of a finished game, or to the setup screen
+    /// when the replay came from a file.
+    pub fn close_replay(app: Arc<Self>) {
+        app.replay.set(None);
+        let over = app.game.lock_ref().phase() == Phase::Over;
+        app.state.set(if over { GameStates::Over } else { GameStates::Initial });
+    }
+
+    pub fn export_replay(&self) -> Result<(), JsValue> {
+        let file = self.replay_file();
+        files::download(&file.file_name(), &file.to_json())
+    }
+
+    fn discard_saved(app: Arc<Self>) {
+        app.saved.set(None);
+        storage::remove(save::STORAGE_KEY);
     }
 
     pub fn state(&self) -> impl Signal<Item = GameStates> {
@@ -164,115 +590,817 @@ impl App {
     }
 
     pub fn go_play(app: Arc<Self>) {
+        App::discard_saved(app.clone());
         app.state.replace_with(|_state| GameStates::Playing);
         app.players.lock_mut()[0].state.set(PlayerState::Playing);
+        app.start_turn();
+        App::schedule_bot(app);
     }
 
-    pub fn add_players(app: Arc<Self>) {
-        let num = app.config.lock_ref().players;
-        let mut players = vec![];
-        for i in 0..num {
-            players.push(Arc::new(Player::new(i)));
-        } 
-        app.players.lock_mut().clear();
-        app.players.lock_mut().replace_cloned(players);
-    }
     fn render(app: Arc<Self>) -> Dom {
-        let cards = render_cards(app.clone());
-
         html! {"main", {
             .class("app")
+            .class_signal("no_motion", app.timing_signal().map(|t| !t.animate))
+            .style_signal("--animate-duration", app.timing_signal().map(|t| format!("{}ms", t.animation)))
+            .global_event(clone!(app => move |_: VisibilityChange| {
+                let hidden = web_sys::window()
+                    .and_then(|w| w.document())
+                    .is_some_and(|d| d.hidden());
+                App::visibility_changed(app.clone(), hidden);
+            }))
+            // Caught on the way down, so the first click can already be heard.
+            .global_event_with_options(&EventOptions { bubbles: false, preventable: false }, |_: events::Click| sound::unlock())
+            .global_event_with_options(&EventOptions { bubbles: false, preventable: false }, |_: events::KeyDown| sound::unlock())
+            .future(app.locale_signal().for_each(clone!(app => move |locale| {
+                let root = web_sys::window()
+                    .and_then(|w| w.document())
+                    .and_then(|d| d.document_element());
+                if let Some(root) = root {
+                    let _ = root.set_attribute("lang", locale.code());
+                }
+                app.rename_players();
+                async {}
+            })))
             .children(&mut [
                 containers::initial::InitialScreen.render(app.clone()),
-                cards
+                render_cards(app.clone()),
+                containers::menu::MenuScreen.render(app.clone()),
+                containers::results::ResultsScreen.render(app.clone()),
+                containers::replay::ReplayScreen.render(app.clone()),
             ])
         }}
     }
 
-    pub fn change_size(app: Arc<Self>, size: usize) {
-        app.config.lock_mut().size = size;
-        app.cards.lock_mut().clear();
+    pub fn change_config<F>(app: Arc<Self>, f: F)
+    where
+        F: FnOnce(&mut Config) -> Config,
+    {
+        app.config.replace_with(f);
+        app.config.lock_mut().daily = None;
+        App::restart(app);
+    }
+
+    pub fn change_size(app: Arc<Self>, rows: usize, columns: usize, set_size: usize) -> Result<(), SizeError> {
+        engine::check_size(rows, columns, set_size)?;
+        {
+            let mut cfg = app.config.lock_mut();
+            cfg.rows = rows;
+            cfg.columns = columns;
+            cfg.set_size = set_size;
+            cfg.daily = None;
+        }
+        App::restart(app);
+        Ok(())
+    }
+
+    pub fn change_seed(app: Arc<Self>, seed: u64) {
+        {
+            let mut cfg = app.config.lock_mut();
+            cfg.seed = seed;
+            cfg.daily = None;
+        }
+        App::restart(app);
+    }
+
+    /// Deals today's daily board and starts playing it.
+    pub fn start_daily(app: Arc<Self>) {
+        let (theme, hints) = {
+            let cfg = app.config.lock_ref();
+            (cfg.theme, cfg.hints)
+        };
+        let day = today();
+        app.config.set(daily::config(day, theme, hints));
+        App::restart(app.clone());
+
+        let claimed = app.daily.lock_mut().start(day);
+        storage::save(daily::STORAGE_KEY, &*app.daily.lock_ref());
+        app.daily_attempt.set(claimed.then_some(day));
+        App::go_play(app);
+    }
+
+    /// Opens a room on the server at `url` for the board set up here, with
+    /// this device's first profile in the first seat.
+    pub fn host_online(app: Arc<Self>, url: &str) {
+        let greeting = ClientMessage::Create {
+            config: app.config.get_cloned(),
+            profile: app.profiles.lock_ref()[0].clone(),
+        };
+        App::connect(app, url, greeting);
+    }
+
+    pub fn join_online(app: Arc<Self>, url: &str, code: &str) {
+        let greeting = ClientMessage::Join {
+            code: code.to_string(),
+            profile: app.profiles.lock_ref()[0].clone(),
+        };
+        App::connect(app, url, greeting);
+    }
+
+    fn connect(app: Arc<Self>, url: &str, greeting: ClientMessage) {
+        app.leave_online();
+        app.online_error.set(None);
+        let connection = Connection::open(url, greeting, clone!(app => move |message| {
+            App::server_message(app.clone(), message);
+        }));
+        app.online.set(Some(connection));
+    }
+
+    /// Gives up the seat in the current room, if any.
+    fn leave_online(&self) {
+        if let Some(connection) = self.online.replace(None) {
+            connection.leave();
+        }
+        self.seats.set(vec![]);
+    }
+
+    /// The seat this device plays online.
+    fn my_seat(&self) -> Option<usize> {
+        self.online.lock_ref().as_ref().and_then(|c| c.ticket()).map(|t| t.seat)
+    }
+
+    /// "Room ABCD · waiting for players (1/2)" and the like; `None` offline.
+    fn online_label(&self) -> impl Signal<Item = Option<String>> {
+        map_ref! {
+            let locale = self.locale_signal(),
+            let status = self.online.signal_cloned().switch(|c| signal::option(c.map(|c| c.status_signal()))),
+            let ticket = self.online.signal_cloned().switch(|c| signal::option(c.map(|c| c.ticket_signal()))),
+            let seats = self.seats.signal_cloned() =>
+            {
+                let room = match ticket {
+                    Some(Some(ticket)) => locale.t_with("online.room", &[("code", &ticket.code)]),
+                    _ => locale.t("online.online"),
+                };
+                let seated = seats.iter().flatten().count();
+                status.map(|status| match status {
+                    Status::Connecting => locale.t("online.connecting"),
+                    Status::Reconnecting => locale.t_with("online.reconnecting", &[("room", &room)]),
+                    Status::Closed => locale.t_with("online.disconnected", &[("room", &room)]),
+                    Status::Connected if seated < seats.len() => locale.t_with(
+                        "online.waiting",
+                        &[("room", &room), ("seated", &seated), ("seats", &seats.len())],
+                    ),
+                    Status::Connected => room,
+                })
+            }
+        }
+    }
+
+    /// Shows what the server sent. The board it sends is the real one, so
+    /// it replaces whatever is on screen.
+    fn server_message(app: Arc<Self>, message: ServerMessage) {
+        match message {
+            ServerMessage::Joined(_) => app.online_error.set(None),
+            ServerMessage::Room { config, seats, game } => {
+                let over = game.phase() == Phase::Over;
+                app.config.set(config);
+                App::load_game(app.clone(), game);
+                app.seats.set(seats);
+                app.name_seats();
+                app.state.set(if over { GameStates::Over } else { GameStates::Playing });
+            }
+            ServerMessage::Update { events, game } => {
+                {
+                    // Turned cards are the only ones whose value is sent.
+                    let cards = app.cards.lock_ref();
+                    for event in &events {
+                        if let GameEvent::Flipped { position } = event {
+                            cards[*position].value.set(game.cards()[*position].value);
+                        }
+                    }
+                }
+                if game.phase() == Phase::Over {
+                    // Only now does the server tell the seed, for the replay.
+                    app.config.lock_mut().seed = game.setup().seed;
+                }
+                app.game.set(game);
+                app.record(app.clock.get().elapsed(now()), &events);
+                app.announce(&events);
+                for event in events {
+                    App::apply_event(app.clone(), event);
+                }
+            }
+            ServerMessage::Error { error } => app.online_error.set(Some(error)),
+            ServerMessage::Rejected { error } => {
+                app.online_error.set(Some(error));
+                app.leave_online();
+                if app.state.get() == GameStates::Playing {
+                    App::setup_new_game(app);
+                }
+            }
+        }
+    }
+
+    /// Puts the names and colors the server knows on the players.
+    fn name_seats(&self) {
+        let seats = self.seats.lock_ref();
+        for (id, player) in self.players.lock_ref().iter().enumerate() {
+            match seats.get(id).and_then(Option::as_ref) {
+                Some(seat) => {
+                    let name = self.seat_name(&seat.profile, id);
+                    player.name.set(if seat.connected { name } else { self.t_with("player.away", &[("name", &name)]) });
+                    player.color.set(seat.profile.color);
+                }
+                None => player.name.set(self.t("player.open_seat")),
+            }
+        }
+    }
+
+    /// Whether the game stands still for the menu. Online it can't: the
+    /// server and the other players carry on.
+    fn paused(&self) -> bool {
+        self.menu.get() != Menu::Closed && self.online.lock_ref().is_none()
+    }
+
+    /// Timers only count while the game is on screen and not paused.
+    fn frozen(&self) -> bool {
+        self.hidden.get() || self.paused()
+    }
+
+    /// Sleeps for `ms` milliseconds of play, so pending reveals and computer
+    /// moves wait while the game is frozen.
+    async fn wait(&self, ms: u32) {
+        let mut left = ms;
+        while left > 0 {
+            let step = left.min(PAUSE_POLL);
+            TimeoutFuture::new(step).await;
+            if !self.frozen() {
+                left -= step;
+            }
+        }
+    }
+
+    /// Runs the clock only while a started game is on screen and unfinished.
+    fn sync_clock(&self) {
+        let running = !self.frozen() && self.game.lock_ref().phase() != Phase::Over;
+        for clock in [&self.clock, &self.shot] {
+            let mut clock = clock.lock_mut();
+            if running {
+                clock.resume(now());
+            } else {
+                clock.pause(now());
+            }
+        }
+    }
+
+    /// Gives the player in turn a fresh shot clock, if the game has one.
+    fn start_turn(&self) {
+        if self.config.lock_ref().shot_clock.is_none() {
+            return;
+        }
+        let mut shot = Clock::default();
+        shot.start(now());
+        if self.frozen() {
+            shot.pause(now());
+        }
+        self.shot.set(shot);
+    }
+
+    /// Counts down the shot clock and passes the turn when it runs out.
+    fn check_shot_clock(app: Arc<Self>) {
+        let limit = match app.config.lock_ref().shot_clock {
+            Some(seconds) => seconds as f64 * 1000.0,
+            None => return,
+        };
+        if !app.shot.get().is_started() {
+            return;
+        }
+
+        let left = limit - app.shot.get().elapsed(now());
+        app.shot_left.set(Some((left.max(0.0) / 1000.0).ceil() as u32));
+        let playing = app.state.get() == GameStates::Playing && app.game.lock_ref().phase() == Phase::Playing;
+        if left <= 0.0 && playing {
+            App::pass(app);
+        }
+    }
+
+    fn pass(app: Arc<Self>) {
+        let events = app.game.lock_mut().pass();
+        app.record(app.clock.get().elapsed(now()), &events);
+        app.watch(&events);
+        app.announce(&events);
+        for event in events {
+            App::apply_event(app.clone(), event);
+        }
+        App::schedule_bot(app);
+    }
+
+    /// Play time as shown, hint penalties included.
+    fn play_time(&self) -> f64 {
+        self.clock.get().elapsed(now()) + self.game.lock_ref().penalty_time()
+    }
+
+    fn tick(&self) {
+        let elapsed = self.play_time();
+        for p in self.players.lock_ref().iter() {
+            if let Some(time) = &p.time {
+                time.set(elapsed);
+            }
+        }
+    }
+
+    /// Compares the finished game with the stored personal best.
+    fn record_result(&self) {
+        let cfg = self.config.get_cloned();
+        let time = self.play_time();
+        let moves = self.game.lock_ref().players().iter().map(|p| p.moves).sum();
+
+        // Bests measure people on this device; a game with a computer seat,
+        // one played online or one with moves undone doesn't count.
+        let computers = self.players.lock_ref().iter().any(|p| p.computer.is_some());
+        let practice = self.practice.get();
+        if !computers && !practice && self.online.lock_ref().is_none() {
+            let mut records = self.records.lock_mut();
+            self.improvement.set(records.submit(&cfg, time, moves));
+            storage::save(records::STORAGE_KEY, &*records);
+        }
+
+        // Undoing gives up the daily attempt rather than earning another.
+        let attempt = self.daily_attempt.replace(None);
+        if let Some(day) = attempt {
+            self.save_daily(day, practice);
+        }
+        self.daily_scored.set(attempt.is_some());
+    }
+
+    /// Stores the day's attempt as it stands on the board.
+    fn save_daily(&self, day: i64, abandoned: bool) {
+        let result = DailyResult {
+            moves: self.game.lock_ref().players().iter().map(|p| p.moves).sum(),
+            time: self.play_time(),
+            outcomes: daily::outcomes(&self.log.lock_ref()),
+            abandoned,
+        };
+        let mut daily = self.daily.lock_mut();
+        daily.update(day, result);
+        storage::save(daily::STORAGE_KEY, &*daily);
+    }
+
+    pub fn reset_records(app: Arc<Self>) {
+        app.records.lock_mut().clear();
+        storage::remove(records::STORAGE_KEY);
+    }
+
+    pub fn open_menu(app: Arc<Self>) {
+        app.menu.set(Menu::Open);
+        app.sync_clock();
+        app.tick();
+    }
+
+    pub fn close_menu(app: Arc<Self>) {
+        app.menu.set(Menu::Closed);
+        app.sync_clock();
+    }
+
+    /// Someone has flipped a card and the game isn't over yet.
+    fn in_progress(&self) -> bool {
+        self.clock.get().is_started() && self.game.lock_ref().phase() != Phase::Over
+    }
+
+    /// Restart or New Game from the game screen. Asks first when that
+    /// would throw away a game in progress.
+    pub fn discard(app: Arc<Self>, discard: Discard) {
+        if app.in_progress() {
+            app.menu.set(Menu::Confirm(discard));
+            app.sync_clock();
+            app.tick();
+        } else {
+            App::confirm_discard(app, discard);
+        }
+    }
+
+    pub fn confirm_discard(app: Arc<Self>, discard: Discard) {
+        match discard {
+            Discard::Restart => App::play_again(app.clone()),
+            Discard::NewGame => App::setup_new_game(app.clone()),
+        }
+        App::close_menu(app);
+    }
+
+    pub fn change_settings<F>(app: Arc<Self>, f: F)
+    where
+        F: FnOnce(&mut Settings),
+    {
+        let mut settings = app.settings.lock_mut();
+        f(&mut settings);
+        storage::save(settings::STORAGE_KEY, &*settings);
+    }
+
+    fn play(&self, sound: Sound) {
+        let settings = self.settings.lock_ref();
+        if !settings.muted && settings.volume > 0.0 {
+            sound::play(sound, settings.volume);
+        }
+    }
+
+    pub fn timing(&self) -> Timing {
+        Timing::new(self.settings.lock_ref().speed, self.reduced_motion.get())
+    }
+
+    fn timing_signal(&self) -> impl Signal<Item = Timing> {
+        map_ref! {
+            let speed = self.settings.signal_ref(|s| s.speed),
+            let reduced = self.reduced_motion.signal() =>
+            Timing::new(*speed, *reduced)
+        }
+    }
+
+    /// The language picked on the setup screen, or else the browser's.
+    pub fn locale(&self) -> Locale {
+        self.settings.lock_ref().locale.unwrap_or_else(|| self.browser_locale.get())
+    }
 
-        for i in 0..size {
-            let ind = i % (size / 2);
-            app.cards
-                .lock_mut()
-                .push_cloned(Arc::new(Card::new(ind, i as u8)));
+    fn locale_signal(&self) -> impl Signal<Item = Locale> {
+        map_ref! {
+            let chosen = self.settings.signal_ref(|s| s.locale),
+            let browser = self.browser_locale.signal() =>
+            chosen.unwrap_or(*browser)
         }
+        .dedupe()
+    }
+
+    /// `text` in the player's language, written again whenever it changes.
+    /// Screens are built once, so every text on them goes through here.
+    fn localized<F>(&self, text: F) -> impl Signal<Item = String>
+    where
+        F: FnMut(Locale) -> String + 'static,
+    {
+        self.locale_signal().map(text)
+    }
+
+    /// `localized` for a message that takes no arguments.
+    fn t_signal(&self, id: &'static str) -> impl Signal<Item = String> {
+        self.localized(move |locale| locale.t(id))
     }
 
+    pub fn t(&self, id: &str) -> String {
+        self.locale().t(id)
+    }
+
+    pub fn t_with(&self, id: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+        self.locale().t_with(id, args)
+    }
+
+    pub fn t_count(&self, id: &str, count: usize, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+        self.locale().t_count(id, count, args)
+    }
+
+    pub fn visibility_changed(app: Arc<Self>, hidden: bool) {
+        app.hidden.set(hidden);
+        // The settings may have changed while the page was away.
+        if !hidden {
+            app.reduced_motion.set_neq(prefers_reduced_motion());
+            app.browser_locale.set_neq(browser_locale());
+        }
+        app.sync_clock();
+        app.tick();
+        if hidden && app.state.get() == GameStates::Playing {
+            app.autosave();
+        }
+    }
+
+    /// A person flipping `c`. Ignored while a computer seat is playing.
+    /// Online the flip is only asked for, on this device's turn, and the
+    /// server answers with the events.
     pub fn game_play(app: Arc<Self>, c: Arc<Card>) {
-        let all_cards = app.cards.lock_ref();
-        let selected_cards = all_cards
-            .iter()
-            .filter(|cc| cc.id != c.id && cc.state.get() == CardState::Selected);
-        let hidden = all_cards.iter().filter(|cc| cc.state.get() == CardState::Hidden);
-        let players = app.players.lock_mut();
-        let player = players.iter().filter(|p| p.id == app.player_in_turn.get());
-
-        if hidden.count() == 0 {
-            web_sys::console::log_1(&format!("GameOVER").into());
-        }
-
-        if selected_cards.clone().count() > 0 {
-            for curr_card in selected_cards {
-                if curr_card.value == c.value {
-                    let c_c = c.clone(); 
-                    let cur_c = curr_card.clone();
-                    spawn_local(async move {
-                        TimeoutFuture::new(300).await;
-                        c_c.state.set(CardState::Fine);
-                        cur_c.state.set(CardState::Fine);
-                    });
-
-                    player.clone().for_each(|p| {
-                        p.points.set(true);
-                        p.score.set(p.score.get() + 1);
-                        let sp = p.clone();
-                        spawn_local(async move {
-                            TimeoutFuture::new(300).await;
-                            sp.points.set(false);
-                        });
-
-                    });
-
-                    let c_fine = c.clone();
-                    let ccur = curr_card.clone();
-                    spawn_local(async move {
-                        TimeoutFuture::new(750).await;
-                        c_fine.state.set(CardState::Shown);
-                        ccur.state.set(CardState::Shown);
-                    });
-                } else {
-                    let cc = c.clone();
-                    let c_card = curr_card.clone();
-                    
-                    let cc_card = curr_card.clone();
-                    let c_cc = c.clone();
-
-                    spawn_local(async move {
-                        TimeoutFuture::new(100).await;
-                        c_cc.state.set(CardState::Wrong);
-                        cc_card.state.set(CardState::Wrong);
-                    });
-
-                    app.player_in_turn.set(
-                        (app.player_in_turn.get() + 1 ) % app.config.lock_ref().players);
-
-                    spawn_local(async move {
-                        TimeoutFuture::new(1000).await;
-                        cc.state.set(CardState::Hidden);
-                        c_card.state.set(CardState::Hidden);
-                    });
+        if app.menu.get() != Menu::Closed {
+            return;
+        }
+        if let Some(connection) = app.online.get_cloned() {
+            let playing = app.game.lock_ref().phase() == Phase::Playing;
+            if playing && app.my_seat() == Some(app.player_in_turn.get()) {
+                connection.send(&ClientMessage::Flip { position: c.id });
+            }
+            return;
+        }
+        if app.computer_in_turn().is_some() {
+            return;
+        }
+        App::flip(app, c.id);
+    }
+
+    /// Briefly shows the player in turn where to look, at a cost.
+    pub fn use_hint(app: Arc<Self>) {
+        if app.computer_in_turn().is_some() || app.menu.get() != Menu::Closed {
+            return;
+        }
+
+        let events = app.game.lock_mut().hint();
+        app.record(app.clock.get().elapsed(now()), &events);
+        app.announce(&events);
+        for event in events {
+            App::apply_event(app.clone(), event);
+        }
+        app.tick();
+    }
+
+    fn flip(app: Arc<Self>, position: usize) {
+        let before = (app.can_rewind() && app.game.lock_ref().selected().is_empty()).then(|| app.snapshot());
+        let at = app.clock.get().elapsed(now());
+        let events = app.game.lock_mut().flip_at(position, at);
+        if let Some(before) = before.filter(|_| !events.is_empty()) {
+            app.history.lock_mut().push(before);
+        }
+
+        app.record(at, &events);
+        app.watch(&events);
+        app.announce(&events);
+        for event in events {
+            App::apply_event(app.clone(), event);
+        }
+        App::schedule_bot(app);
+    }
+
+    /// Undo is for practising alone; it would make no sense against others.
+    fn can_rewind(&self) -> bool {
+        self.config.lock_ref().players == 1 && self.online.lock_ref().is_none()
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        let mut clock = self.clock.get();
+        clock.pause(now());
+        Snapshot {
+            game: self.game.get_cloned(),
+            log: self.log.get_cloned(),
+            clock,
+        }
+    }
+
+    /// Takes back the last attempt, or the cards turned so far in this one.
+    pub fn undo(app: Arc<Self>) {
+        App::rewind(app, History::undo, "announce.undone");
+    }
+
+    pub fn redo(app: Arc<Self>) {
+        App::rewind(app, History::redo, "announce.redone");
+    }
+
+    /// Swaps the game on screen for a snapshot taken by `step`. Only
+    /// between attempts, so no reveal is left pending. The game becomes
+    /// practice from then on. `message` is the id announced.
+    fn rewind<F>(app: Arc<Self>, step: F, message: &str)
+    where
+        F: FnOnce(&mut History<Snapshot>, Snapshot) -> Option<Snapshot>,
+    {
+        let playing = app.game.lock_ref().phase() == Phase::Playing;
+        if !app.can_rewind() || !playing || app.menu.get() != Menu::Closed {
+            return;
+        }
+        let current = app.snapshot();
+        let snapshot = match step(&mut app.history.lock_mut(), current) {
+            Some(snapshot) => snapshot,
+            None => return,
+        };
+
+        app.practice.set(true);
+        App::load_game(app.clone(), snapshot.game);
+        app.log.set(snapshot.log);
+        app.clock.set(snapshot.clock);
+        app.sync_clock();
+        app.tick();
+        app.announcement.set(app.t(message));
+        app.autosave();
+    }
+
+    fn computer_in_turn(&self) -> Option<Difficulty> {
+        self.players.lock_ref().get(self.player_in_turn.get()).and_then(|p| p.computer)
+    }
+
+    /// Shows `events` to every computer seat.
+    fn watch(&self, events: &[GameEvent]) {
+        let game = self.game.lock_ref();
+        for bot in self.bots.lock_mut().iter_mut().flatten() {
+            bot.watch(&game, events);
+        }
+    }
+
+    /// Lets a computer seat take its next flip, slowly enough for people
+    /// to follow. Waits while the game is frozen and gives up if the board
+    /// was dealt again meanwhile.
+    fn schedule_bot(app: Arc<Self>) {
+        let playing = app.state.get() == GameStates::Playing && app.game.lock_ref().phase() == Phase::Playing;
+        if !playing || app.computer_in_turn().is_none() {
+            return;
+        }
+
+        let dealt = app.cards.lock_ref().first().cloned();
+        spawn_local(async move {
+            app.wait(app.timing().bot).await;
+
+            let current = app.cards.lock_ref().first().cloned();
+            let same_deal = match (current, dealt) {
+                (Some(current), Some(dealt)) => Arc::ptr_eq(&current, &dealt),
+                _ => false,
+            };
+            if !same_deal || app.state.get() != GameStates::Playing {
+                return;
+            }
+
+            let choice = {
+                let game = app.game.lock_ref();
+                match app.bots.lock_ref().get(game.turn()) {
+                    Some(Some(bot)) if game.phase() == Phase::Playing => bot.choose(&game, &mut thread_rng()),
+                    _ => None,
+                }
+            };
+            if let Some(position) = choice {
+                App::flip(app, position);
+            }
+        });
+    }
+
+    /// Moves the roving focus on the board and flips with Enter or Space.
+    fn board_key(app: Arc<Self>, position: usize, key: &str) -> bool {
+        let direction = match key {
+            "ArrowUp" => Direction::Up,
+            "ArrowDown" => Direction::Down,
+            "ArrowLeft" => Direction::Left,
+            "ArrowRight" => Direction::Right,
+            "Enter" | " " => {
+                let card = app.cards.lock_ref().get(position).cloned();
+                if let Some(card) = card {
+                    App::game_play(app, card);
                 }
+                return true;
             }
-            player.for_each(|data| data.moves.set(data.moves.get() + 1));
+            _ => return false,
+        };
+
+        let columns = app.config.lock_ref().columns;
+        let len = app.cards.lock_ref().len();
+        app.focus.set(grid::step(position, direction, columns, len));
+        true
+    }
+
+    fn face_label(&self, value: usize) -> String {
+        face_label(self.locale(), self.config.lock_ref().theme, value)
+    }
+
+    /// Adds `events` to the move log, `at` milliseconds of play in. Takes
+    /// the time the game was given, so a replay scores the same.
+    fn record(&self, at: f64, events: &[GameEvent]) {
+        replay::record(&mut self.log.lock_mut(), at, events);
+    }
+
+    /// Sends a summary of `events` to the screen reader live region.
+    fn announce(&self, events: &[GameEvent]) {
+        let messages: Vec<String> = events
+            .iter()
+            .filter_map(|event| match event {
+                GameEvent::Flipped { position } => {
+                    let value = self.cards.lock_ref()[*position].value.get();
+                    Some(self.face_label(value))
+                }
+                GameEvent::Matched { player, positions } => {
+                    let value = self.cards.lock_ref()[positions[0]].value.get();
+                    let set = self.locale().set_name(positions.len(), 1);
+                    Some(self.t_with(
+                        "announce.match",
+                        &[("name", &self.player_name(*player)), ("face", &self.face_label(value)), ("set", &set)],
+                    ))
+                }
+                GameEvent::Mismatched { .. } => Some(self.t("announce.miss")),
+                GameEvent::Hinted { positions, .. } => {
+                    let cards = self.cards.lock_ref();
+                    let shown: Vec<String> = positions
+                        .iter()
+                        .map(|&p| {
+                            let face = self.face_label(cards[p].value.get());
+                            self.t_with("announce.hint_card", &[("number", &(p + 1)), ("face", &face)])
+                        })
+                        .collect();
+                    Some(self.t_with("announce.hint", &[("cards", &shown.join(", "))]))
+                }
+                GameEvent::TimedOut { player } => {
+                    Some(self.t_with("announce.timed_out", &[("name", &self.player_name(*player))]))
+                }
+                GameEvent::TurnChanged { player } => Some(self.t_with("turn.of", &[("name", &self.player_name(*player))])),
+                GameEvent::Over => Some(self.t("announce.over")),
+                _ => None,
+            })
+            .collect();
+
+        if !messages.is_empty() {
+            self.announcement.set(messages.join(". "));
+        }
+    }
+
+    /// Lets the engine settle the pair once its animation is over. `cards`
+    /// are the pair being resolved; if the board was dealt again meanwhile
+    /// they are stale and nothing happens. Online the server settles it.
+    fn resolve(app: Arc<Self>, cards: &[Arc<Card>]) {
+        if app.online.lock_ref().is_some() {
+            return;
+        }
+
+        let current = match cards.first() {
+            Some(card) => app.cards.lock_ref().get(card.id).cloned(),
+            None => return,
+        };
+        if !current.is_some_and(|c| Arc::ptr_eq(&c, &cards[0])) {
+            return;
         }
+
+        let at = app.clock.get().elapsed(now());
+        let events = app.game.lock_mut().resolve_at(at);
+        app.record(at, &events);
+        app.watch(&events);
+        app.announce(&events);
+        for event in events {
+            App::apply_event(app.clone(), event);
+        }
+        app.autosave();
+        App::schedule_bot(app);
+    }
+
+    fn cards_at(&self, positions: &[usize]) -> Vec<Arc<Card>> {
+        let cards = self.cards.lock_ref();
+        positions.iter().map(|&p| cards[p].clone()).collect()
     }
 
-    pub fn card_selection(_app: Arc<Self>, card: Arc<Card>) {
-        if card.state.get() == CardState::Hidden {
-            card.state.set(CardState::Selected);
+    fn apply_event(app: Arc<Self>, event: GameEvent) {
+        match event {
+            GameEvent::Flipped { position } => {
+                app.clock.lock_mut().start(now());
+                app.cards.lock_ref()[position].state.set(CardState::Selected);
+                app.play(Sound::Flip);
+            }
+            GameEvent::Matched { positions, .. } => {
+                let cards = app.cards_at(&positions);
+                let timing = app.timing();
+                spawn_local(async move {
+                    app.wait(timing.match_reveal).await;
+                    cards.iter().for_each(|c| c.settle(CardState::Fine));
+                    app.play(Sound::Match);
+                    app.wait(timing.match_hold).await;
+                    App::resolve(app, &cards);
+                });
+            }
+            GameEvent::Mismatched { positions, .. } => {
+                let cards = app.cards_at(&positions);
+                let timing = app.timing();
+                spawn_local(async move {
+                    app.wait(timing.miss_reveal).await;
+                    cards.iter().for_each(|c| c.settle(CardState::Wrong));
+                    app.play(Sound::Mismatch);
+                    app.wait(timing.miss_hold).await;
+                    App::resolve(app, &cards);
+                });
+            }
+            GameEvent::Settled { positions } => {
+                for card in app.cards_at(&positions) {
+                    card.state.set(CardState::Shown);
+                }
+                app.start_turn();
+            }
+            GameEvent::Hidden { positions } => {
+                for card in app.cards_at(&positions) {
+                    card.state.set(CardState::Hidden);
+                }
+            }
+            GameEvent::Scored { player, score } => {
+                let p = app.players.lock_ref()[player].clone();
+                p.points.set(true);
+                p.score.set(score);
+                let points = app.timing().points;
+                spawn_local(async move {
+                    TimeoutFuture::new(points).await;
+                    p.points.set(false);
+                });
+            }
+            GameEvent::Moved { player, moves } => {
+                app.players.lock_ref()[player].moves.set(moves);
+            }
+            GameEvent::TurnChanged { player } => {
+                app.player_in_turn.set(player);
+                app.play(Sound::TurnChange);
+                app.start_turn();
+            }
+            GameEvent::TimedOut { .. } => {}
+            GameEvent::Hinted { player, positions, hints } => {
+                app.players.lock_ref()[player].hints.set(hints);
+                let cards = app.cards_at(&positions);
+                cards.iter().for_each(|c| c.state.set(CardState::Hinted));
+                spawn_local(async move {
+                    app.wait(HINT_REVEAL).await;
+                    for card in cards.iter().filter(|c| c.state.get() == CardState::Hinted) {
+                        card.state.set(CardState::Hidden);
+                    }
+                });
+            }
+            GameEvent::Over => {
+                app.menu.set(Menu::Closed);
+                app.sync_clock();
+                app.tick();
+                app.record_result();
+                storage::remove(save::STORAGE_KEY);
+                app.play(Sound::Victory);
+                spawn_local(async move {
+                    app.wait(app.timing().results).await;
+                    if app.game.lock_ref().phase() == Phase::Over {
+                        app.state.set(GameStates::Over);
+                    }
+                });
+            }
         }
     }
 
@@ -287,25 +1415,104 @@ pub fn render_cards(app: Arc<App>) -> Dom {
 
     html! {"section", {
         .class(base)
-            .visible_signal(app.state().map(|s| s == GameStates::Playing))
+            .visible_signal(app.state().map(|s| s == GameStates::Playing || s == GameStates::Over))
             .children(&mut [
                 html!{"div", {
                     .class(format!("{}_top", base))
                     .children(&mut [
-                        html!{"h1", {
-                            .class(format!("{}_title", base))
-                            .text("memory")
+                        html!{"div", {
+                            .children(&mut [
+                                html!{"h1", {
+                                    .class(format!("{}_title", base))
+                                    .text_signal(app.t_signal("app.title"))
+                                }},
+                                html!{"p", {
+                                    .class(format!("{}_seed", base))
+                                    .text_signal(map_ref! {
+                                        let locale = app.locale_signal(),
+                                        let cfg = app.config.signal_cloned(),
+                                        let online = app.online_label(),
+                                        let practice = app.practice.signal() => {
+                                            let label = match (online, cfg.daily) {
+                                                (Some(online), _) => online.clone(),
+                                                (None, Some(day)) => locale.t_with("game.daily", &[("date", &daily::date(day))]),
+                                                (None, None) => locale.t_with("game.board", &[("seed", &cfg.seed)]),
+                                            };
+                                            if *practice { locale.t_with("game.practice", &[("label", &label)]) } else { label }
+                                        }
+                                    })
+                                }},
+                                html!{"p", {
+                                    .class(format!("{}_error", base))
+                                    .attr("role", "alert")
+                                    .visible_signal(app.online_error.signal_ref(Option::is_some))
+                                    .text_signal(map_ref! {
+                                        let locale = app.locale_signal(),
+                                        let error = app.online_error.signal_cloned() =>
+                                        error.as_ref().map(|e| locale.online_error(e)).unwrap_or_default()
+                                    })
+                                }},
+                            ])
                         }},
                         html!{"div", {
                             .class(format!("{}_options", base))
                             .children(&mut [
+                                html!{"button", {
+                                    .class("btn")
+                                    .class("bg_gray_100")
+                                    .visible_signal(app.config.signal_ref(|cfg| cfg.players == 1))
+                                    .text_signal(app.t_signal("game.undo"))
+                                    .prop_signal("disabled", map_ref! {
+                                        let history = app.history.signal_ref(History::can_undo),
+                                        let phase = app.game.signal_ref(Game::phase) =>
+                                        !*history || *phase != Phase::Playing
+                                    })
+                                    .event(clone!(app => move |_: events::Click| {
+                                        App::undo(app.clone());
+                                    }))
+                                }},
+                                html!{"button", {
+                                    .class("btn")
+                                    .class("bg_gray_100")
+                                    .visible_signal(app.config.signal_ref(|cfg| cfg.players == 1))
+                                    .text_signal(app.t_signal("game.redo"))
+                                    .prop_signal("disabled", map_ref! {
+                                        let history = app.history.signal_ref(History::can_redo),
+                                        let phase = app.game.signal_ref(Game::phase) =>
+                                        !*history || *phase != Phase::Playing
+                                    })
+                                    .event(clone!(app => move |_: events::Click| {
+                                        App::redo(app.clone());
+                                    }))
+                                }},
+                                html!{"button", {
+                                    .class("btn")
+                                    .class("bg_gray_100")
+                                    .visible_signal(app.config.signal_ref(|cfg| cfg.hints > 0))
+                                    .text_signal(map_ref! {
+                                        let locale = app.locale_signal(),
+                                        let left = app.game.signal_ref(|g| g.hints_left(g.turn())) =>
+                                        locale.t_with("game.hint", &[("left", left)])
+                                    })
+                                    .prop_signal("disabled", map_ref! {
+                                        let game = app.game.signal_cloned(),
+                                        let cfg = app.config.signal_cloned() =>
+                                        game.phase() != Phase::Playing
+                                            || game.hints_left(game.turn()) == 0
+                                            || cfg.computers[game.turn()].is_some()
+                                    })
+                                    .event(clone!(app => move |_: events::Click| {
+                                        App::use_hint(app.clone());
+                                    }))
+                                }},
                                 html!{"button", {
                                     .class("btn")
                                     .class("bg_orange")
-                                    .text("Restart")
+                                    .class("wide_only")
+                                    .visible_signal(app.online.signal_ref(Option::is_none))
+                                    .text_signal(app.t_signal("game.restart"))
                                     .event(clone!(app => move |_: events::Click| {
-                                        App::restart(app.clone());
-                                        app.state.replace_with(|_state| GameStates::Playing);
+                                        App::discard(app.clone(), Discard::Restart);
                                     }))
 
                                 }},
@@ -313,10 +1520,20 @@ pub fn render_cards(app: Arc<App>) -> Dom {
                                 html!{"button", {
                                     .class("btn")
                                     .class("bg_gray_100")
-                                    .text("New Game")
+                                    .class("wide_only")
+                                    .text_signal(app.t_signal("game.new"))
+                                    .event(clone!(app => move |_: events::Click| {
+                                        App::discard(app.clone(), Discard::NewGame);
+                                    }))
+                                }},
+
+                                html!{"button", {
+                                    .class("btn")
+                                    .class("bg_gray_100")
+                                    .attr("aria-haspopup", "dialog")
+                                    .text_signal(app.t_signal("game.menu"))
                                     .event(clone!(app => move |_: events::Click| {
-                                        App::restart(app.clone());
-                                        app.state.replace_with(|_state| GameStates::Initial);
+                                        App::open_menu(app.clone());
                                     }))
                                 }},
 
@@ -325,72 +1542,26 @@ pub fn render_cards(app: Arc<App>) -> Dom {
                     ])
                 }},
 
+                render_board(app.clone(), &app.config, &app.cards, true),
+
                 html!{"div", {
-                    .class(format!("{}_board", base))
-                    .class_signal(
-                        format!("{}_board__four",base),
-                        app.config.signal_cloned().map(|c| c.size == 16))
-                    .class_signal(
-                        format!("{}_board__six",base),
-                        app.config.signal_cloned().map(|c| c.size == 36))
-                    .children_signal_vec(
-                        app.cards.signal_vec_cloned()
-                        .sort_by_cloned(|_,_| {
-                            let mut rng = thread_rng();
-                            match rng.gen_bool(0.5) {
-                                true => Ordering::Less,
-                                false => Ordering::Greater,
-                            }
-                        })
-                        .map(
-                            clone!(
-                                app => move |card| {
-                                    let c = card.clone();
-                                    html!{"div", {
-                                        .class("cell")
-                                        .class("animate__faster")
-                                        .class("animate__animated")
-                                        .class_signal("selected", c.state.signal().map(|s| s == CardState::Selected || s == CardState::Wrong))
-                                        .class_signal("animate__flip", c.state.signal().map(|s| s == CardState::Selected ))
-                                        .class_signal("wrong", c.state.signal().map(|s| s == CardState::Wrong ))
-                                        .class_signal("fine", c.state.signal().map(|s| s == CardState::Fine))
-                                        .class_signal("shown", c.state.signal().map(|s| s == CardState::Shown))
-
-                                        .class_signal("animate__flip", c.state.signal().map(|s| s == CardState::Hidden))
-                                        .future(
-                                            c.state.signal_cloned().for_each(clone!(c, app => move |change| {
-                                                if change == CardState::Selected {
-                                                    App::game_play(app.clone(), c.clone());
-                                                }
-                                               ready(())
-                                            }))
-                                        )
-                                        .event(clone!(app => move |_:events::Click| {
-                                            if c.state.get()  != CardState::Shown {
-                                                App::card_selection(app.clone(), c.clone());
-                                            }
-                                        }))
-                                        .children(&mut [
-                                            html!{"div", {
-                                                .class("card")
-                                                    .children(&mut[
-                                                        html!{"span", {
-                                                            .class("card_value")
-                                                            .text(&format!("{}", card.value))
-
-                                                        }}
-                                                    ])
-                                            }}
-                                        ])
-                                    }}
-                                }
-                            )
-                        )
-                    )
+                    .class("visually_hidden")
+                    .attr("role", "status")
+                    .attr("aria-live", "polite")
+                    .text_signal(app.announcement.signal_cloned())
                 }},
 
                 html!{"div", {
-                    .class(format!("{}_players",base))                     
+                    .class(format!("{}_players",base))
+                    .future(clone!(app => async move {
+                        loop {
+                            TimeoutFuture::new(250).await;
+                            if app.clock.get().is_running() {
+                                app.tick();
+                            }
+                            App::check_shot_clock(app.clone());
+                        }
+                    }))
                     .children(&mut [
                         html!{"ul", {
                             .class("players_list")
@@ -399,14 +1570,22 @@ pub fn render_cards(app: Arc<App>) -> Dom {
                             .class_signal("three_p", app.config.signal_cloned().map(|cfg| cfg.players == 3))
                             .class_signal("four_p", app.config.signal_cloned().map(|cfg| cfg.players == 4))
                             .children_signal_vec(app.players.signal_vec_cloned()
+                                .filter_map(|p| p.time.clone())
+                                .map(clone!(app => move |time| render_stat(app.t_signal("stat.time"), time.signal().map(format_time)))))
+                            .children_signal_vec(app.players.signal_vec_cloned()
+                                .filter(|p| p.time.is_some())
+                                .map(clone!(app => move |p| render_stat(app.t_signal("stat.moves"), p.moves.signal().map(|m| format!("{}", m))))))
+                            .children_signal_vec(app.players.signal_vec_cloned()
+                                .filter(|p| p.time.is_none())
                                 .map(clone!(app => move |p| html!{"li", {
                                     .class_signal("in_turn", app.player_in_turn.signal_cloned().map(clone!( p => move |s| s == p.id)))
                                     .class("players_list__item")
                                     .class("animate__animated")
                                     .children(&mut[
+                                        render_avatar(&p),
                                         html!{"p", {
                                             .class("player-name")
-                                            .text(&format!("Player{}", p.id + 1))
+                                            .text_signal(p.name.signal_cloned())
                                         }},
                                         html!{"p", {
                                             .class_signal("animate__bounceIn", p.points.signal_cloned().map(|s| s))
@@ -417,11 +1596,18 @@ pub fn render_cards(app: Arc<App>) -> Dom {
                                     ])
                                 }})))
                             .children_signal_vec(app.players.signal_vec_cloned()
+                                 .filter(|p| p.time.is_none())
                                  .map(clone!(app => move |p| html!{"p",{
                                     .children(&mut[
                                         html!{"span",{ 
                                             .visible_signal(app.player_in_turn.signal_cloned().map(clone!(p => move |s| s == p.id)))
-                                            .text("Current turn")
+                                            .text_signal(map_ref! {
+                                                let locale = app.locale_signal(),
+                                                let name = p.name.signal_cloned(),
+                                                let extra = app.game.signal_ref(|g| (g.extra_turns(), g.setup().turns)),
+                                                let left = app.shot_left.signal() =>
+                                                turn_label(*locale, name, p.computer.is_some(), extra.0, extra.1, *left)
+                                            })
 
                                         }}
                                      ])
@@ -433,6 +1619,198 @@ pub fn render_cards(app: Arc<App>) -> Dom {
     }}
 }
 
+/// "Ada's turn · extra go 1 of 2 · 8s" for the turn indicator.
+fn turn_label(
+    locale: Locale,
+    name: &str,
+    computer: bool,
+    extra: u32,
+    rule: TurnRule,
+    seconds_left: Option<u32>,
+) -> String {
+    let mut label = if computer {
+        locale.t_with("turn.thinking", &[("name", &name)])
+    } else {
+        locale.t_with("turn.of", &[("name", &name)])
+    };
+    match rule {
+        TurnRule::Capped(max) if extra > 0 => {
+            label = locale.t_with("turn.extra", &[("label", &label), ("extra", &extra), ("max", &max)]);
+        }
+        TurnRule::KeepOnMatch if extra > 0 => label = locale.t_with("turn.again", &[("label", &label)]),
+        _ => {}
+    }
+    if let Some(seconds) = seconds_left {
+        label = locale.t_with("turn.seconds", &[("label", &label), ("seconds", &seconds)]);
+    }
+    label
+}
+
+/// The grid of `cards`, laid out with the configured columns. Only the live
+/// board is `interactive`; the replay shows the same cells without handlers.
+fn render_board(app: Arc<App>, config: &Mutable<Config>, cards: &MutableVec<Arc<Card>>, interactive: bool) -> Dom {
+    let base = "game_board";
+
+    html!{"div", {
+        .class(base)
+        .apply_if(interactive, |dom| apply_methods!(dom, {
+            .class_signal("resolving", app.game.signal_ref(|g| g.phase() == Phase::Resolving))
+            .class_signal("covered", app.menu.signal_ref(|m| *m != Menu::Closed))
+            .attr_signal("aria-hidden", app.menu.signal_ref(|m| if *m != Menu::Closed { Some("true") } else { None }))
+        }))
+        .style_signal("--rows", config.signal_ref(|c| c.rows.to_string()))
+        .style_signal("--columns", config.signal_ref(|c| c.columns.to_string()))
+        .attr("role", "grid")
+        .attr_signal("aria-label", app.t_signal(if interactive { "board.live" } else { "board.replay" }))
+        .children_signal_vec(
+            cards.signal_vec_cloned()
+            .to_signal_cloned()
+            .map(clone!(app, config => move |cards| {
+                let columns = config.lock_ref().columns.max(1);
+                cards.chunks(columns)
+                    .map(|row| html!{"div", {
+                        .class(format!("{}__row", base))
+                        .attr("role", "row")
+                        .children(row.iter().map(|card| render_card(app.clone(), config.clone(), card.clone(), interactive)))
+                    }})
+                    .collect::<Vec<_>>()
+            }))
+            .to_signal_vec()
+        )
+    }}
+}
+
+/// What a card shows, in words, for screen readers.
+fn face_label(locale: Locale, theme: GameTheme, value: usize) -> String {
+    match theme.icon_set() {
+        Some(set) => locale.t(&format!("icon.{}", set.icon(value).name)),
+        None => format!("{}", value),
+    }
+}
+
+fn render_card(app: Arc<App>, config: Mutable<Config>, card: Arc<Card>, interactive: bool) -> Dom {
+    let position = card.id;
+
+    html!{"div", {
+        .class("cell")
+        .class("animate__faster")
+        .class("animate__animated")
+        .class_signal("selected", card.state.signal().map(|s| s == CardState::Selected || s == CardState::Wrong))
+        .class_signal("animate__flip", card.state.signal().map(|s| s == CardState::Selected ))
+        .class_signal("wrong", card.state.signal().map(|s| s == CardState::Wrong ))
+        .class_signal("fine", card.state.signal().map(|s| s == CardState::Fine))
+        .class_signal("shown", card.state.signal().map(|s| s == CardState::Shown))
+        .class_signal("hinted", card.state.signal().map(|s| s == CardState::Hinted))
+
+        .class_signal("animate__flip", card.state.signal().map(|s| s == CardState::Hidden))
+        .attr("role", "gridcell")
+        .attr_signal("aria-label", map_ref! {
+            let locale = app.locale_signal(),
+            let state = card.state.signal(),
+            let theme = config.signal_ref(|cfg| cfg.theme),
+            let value = card.value.signal() => {
+                let number = position + 1;
+                let face = face_label(*locale, *theme, *value);
+                match state {
+                    CardState::Hidden => locale.t_with("card.down", &[("number", &number)]),
+                    CardState::Shown => locale.t_with("card.matched", &[("number", &number), ("face", &face)]),
+                    _ => locale.t_with("card.up", &[("number", &number), ("face", &face)]),
+                }
+            }
+        })
+        .apply_if(interactive, clone!(app, card => move |dom| apply_methods!(dom, {
+            .attr_signal("tabindex", app.focus.signal().map(move |f| if f == position { "0" } else { "-1" }))
+            .focused_signal(app.focus.signal().map(move |f| f == position))
+            .event(clone!(app, card => move |_:events::Click| {
+                app.focus.set(position);
+                App::game_play(app.clone(), card.clone());
+            }))
+            .event_with_options(&EventOptions::preventable(), clone!(app => move |e: events::KeyDown| {
+                if App::board_key(app.clone(), position, &e.key()) {
+                    e.prevent_default();
+                }
+            }))
+        })))
+        .children(&mut [
+            html!{"div", {
+                .class("card")
+                .attr("aria-hidden", "true")
+                .child_signal(map_ref! {
+                    let theme = config.signal_ref(|cfg| cfg.theme).dedupe(),
+                    let value = card.value.signal() =>
+                    Some(render_face(*theme, *value))
+                })
+            }}
+        ])
+    }}
+}
+
+fn render_face(theme: GameTheme, value: usize) -> Dom {
+    match theme.icon_set() {
+        Some(set) => html!{"span", {
+            .class("card_value")
+            .class("card_icon")
+            .children(&mut [set.render(value)])
+        }},
+        None => html!{"span", {
+            .class("card_value")
+            .text(&format!("{}", value))
+        }},
+    }
+}
+
+/// A colored badge with the first letter of the player's name.
+fn render_avatar(player: &Player) -> Dom {
+    render_badge(
+        player.name.signal_ref(|name| profiles::initial(name)),
+        player.color.signal().map(|c| c.css()),
+    )
+}
+
+fn render_badge<T, C>(initial: T, color: C) -> Dom
+where
+    T: Signal<Item = String> + 'static,
+    C: Signal<Item = &'static str> + 'static,
+{
+    html!{"span", {
+        .class("avatar")
+        .attr("aria-hidden", "true")
+        .style_signal("background-color", color)
+        .text_signal(initial)
+    }}
+}
+
+fn render_stat<L, S>(label: L, value: S) -> Dom
+where
+    L: Signal<Item = String> + 'static,
+    S: Signal<Item = String> + 'static,
+{
+    html!{"li", {
+        .class("players_list__item")
+        .children(&mut[
+            html!{"p", {
+                .class("player-name")
+                .text_signal(label)
+            }},
+            html!{"p", {
+                .class("player-score")
+                .text_signal(value)
+            }}
+        ])
+    }}
+}
+
+/// `visibilitychange` bubbles from the document up to the window.
+struct VisibilityChange;
+
+impl StaticEvent for VisibilityChange {
+    const EVENT_TYPE: &'static str = "visibilitychange";
+
+    fn unchecked_from_event(_event: web_sys::Event) -> Self {
+        VisibilityChange
+    }
+}
+
 #[wasm_bindgen(start)]
 pub async fn main_js() -> Result<(), JsValue> {
     #[cfg(debug_assertions)]
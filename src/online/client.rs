@@ -0,0 +1,263 @@
+//! The browser end of a room: one WebSocket to the server, opened again
+//! with the seat's `Ticket` whenever it drops.
+//!
+//! The socket and its callbacks are JavaScript objects that can't be
+//! shared across threads, so they are kept in a thread local next to the
+//! `Connection` that owns them.
+
+use futures_signals::signal::{Mutable, Signal};
+use gloo_timers::future::TimeoutFuture;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{CloseEvent, MessageEvent, WebSocket};
+
+use super::protocol::{ClientMessage, OnlineError, ServerMessage, Ticket, DEFAULT_PORT};
+
+/// First wait before reconnecting, doubled after every failed attempt up
+/// to `MAX_RETRY_DELAY`. In milliseconds.
+const RETRY_DELAY: u32 = 500;
+const MAX_RETRY_DELAY: u32 = 8000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Connecting,
+    Connected,
+    Reconnecting,
+    Closed,
+}
+
+pub struct Connection {
+    id: u64,
+    url: String,
+    /// Sent on the first connect; later ones send `Rejoin` instead.
+    greeting: ClientMessage,
+    handler: Box<dyn Fn(ServerMessage) + Send + Sync>,
+    status: Mutable<Status>,
+    ticket: Mutable<Option<Ticket>>,
+    attempts: Mutable<u32>,
+}
+
+struct Socket {
+    ws: WebSocket,
+    _on_open: Closure<dyn FnMut()>,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+    _on_close: Closure<dyn FnMut(CloseEvent)>,
+}
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    static SOCKETS: RefCell<BTreeMap<u64, Socket>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+/// The server on the machine that served the page, at the default port.
+/// Pages served over https can only open secure sockets.
+pub fn default_url() -> String {
+    let location = web_sys::window().map(|w| w.location());
+    let host = location
+        .as_ref()
+        .and_then(|l| l.hostname().ok())
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| "localhost".to_string());
+    let secure = location.and_then(|l| l.protocol().ok()).is_some_and(|p| p == "https:");
+    format!("{}://{}:{}", if secure { "wss" } else { "ws" }, host, DEFAULT_PORT)
+}
+
+impl fmt::Debug for Connection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Connection")
+            .field("url", &self.url)
+            .field("status", &self.status.get())
+            .field("ticket", &self.ticket.get_cloned())
+            .finish()
+    }
+}
+
+impl Drop for Socket {
+    fn drop(&mut self) {
+        self.ws.set_onopen(None);
+        self.ws.set_onmessage(None);
+        self.ws.set_onclose(None);
+        let _ = self.ws.close();
+    }
+}
+
+impl Connection {
+    /// Connects to `url` and sends `greeting` once the socket is open.
+    /// Every message from the server goes to `handler`, and so does a
+    /// `Rejected` when it can't be reached at all. It is never called
+    /// before `open` returns.
+    pub fn open<F>(url: &str, greeting: ClientMessage, handler: F) -> Arc<Self>
+    where
+        F: Fn(ServerMessage) + Send + Sync + 'static,
+    {
+        let connection = Arc::new(Connection {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            url: url.to_string(),
+            greeting,
+            handler: Box::new(handler),
+            status: Mutable::new(Status::Connecting),
+            ticket: Mutable::new(None),
+            attempts: Mutable::new(0),
+        });
+        Connection::connect(&connection);
+        connection
+    }
+
+    pub fn status_signal(&self) -> impl Signal<Item = Status> {
+        self.status.signal()
+    }
+
+    pub fn ticket(&self) -> Option<Ticket> {
+        self.ticket.get_cloned()
+    }
+
+    pub fn ticket_signal(&self) -> impl Signal<Item = Option<Ticket>> {
+        self.ticket.signal_cloned()
+    }
+
+    /// Sends `message` if the socket is open; otherwise it is dropped, the
+    /// server sends the whole room again after a reconnect anyway.
+    pub fn send(&self, message: &ClientMessage) {
+        let text = match serde_json::to_string(message) {
+            Ok(text) => text,
+            Err(_) => return,
+        };
+        SOCKETS.with(|sockets| {
+            if let Some(socket) = sockets.borrow().get(&self.id) {
+                if socket.ws.ready_state() == WebSocket::OPEN {
+                    let _ = socket.ws.send_with_str(&text);
+                }
+            }
+        });
+    }
+
+    /// Gives up the seat and hangs up for good.
+    pub fn leave(&self) {
+        self.send(&ClientMessage::Leave);
+        self.status.set(Status::Closed);
+        SOCKETS.with(|sockets| sockets.borrow_mut().remove(&self.id));
+    }
+
+    fn connect(this: &Arc<Self>) {
+        let ws = match WebSocket::new(&this.url) {
+            Ok(ws) => ws,
+            Err(_) => {
+                let error = OnlineError::BadAddress(this.url.clone());
+                let this = this.clone();
+                spawn_local(async move {
+                    this.receive(ServerMessage::Rejected { error });
+                });
+                return;
+            }
+        };
+
+        let weak = Arc::downgrade(this);
+        let on_open = Closure::wrap(Box::new(move || {
+            if let Some(this) = weak.upgrade() {
+                this.attempts.set(0);
+                this.status.set(Status::Connected);
+                let greeting = match this.ticket() {
+                    Some(ticket) => ClientMessage::Rejoin(ticket),
+                    None => this.greeting.clone(),
+                };
+                this.send(&greeting);
+            }
+        }) as Box<dyn FnMut()>);
+
+        // The handlers below run a tick later, so that whatever they do,
+        // including dropping this connection, happens outside the callback.
+        let weak = Arc::downgrade(this);
+        let on_message = Closure::wrap(Box::new(move |event: MessageEvent| {
+            let message = event
+                .data()
+                .as_string()
+                .and_then(|text| serde_json::from_str::<ServerMessage>(&text).ok());
+            if let Some(message) = message {
+                let weak = weak.clone();
+                spawn_local(async move {
+                    if let Some(this) = weak.upgrade() {
+                        this.receive(message);
+                    }
+                });
+            }
+        }) as Box<dyn FnMut(MessageEvent)>);
+
+        let weak = Arc::downgrade(this);
+        let on_close = Closure::wrap(Box::new(move |_: CloseEvent| {
+            let weak = weak.clone();
+            spawn_local(async move {
+                if let Some(this) = weak.upgrade() {
+                    Connection::dropped(this);
+                }
+            });
+        }) as Box<dyn FnMut(CloseEvent)>);
+
+        ws.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+        ws.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        ws.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+
+        let socket = Socket {
+            ws,
+            _on_open: on_open,
+            _on_message: on_message,
+            _on_close: on_close,
+        };
+        SOCKETS.with(|sockets| sockets.borrow_mut().insert(this.id, socket));
+    }
+
+    fn receive(&self, message: ServerMessage) {
+        match &message {
+            ServerMessage::Joined(ticket) => self.ticket.set(Some(ticket.clone())),
+            ServerMessage::Rejected { .. } => {
+                self.status.set(Status::Closed);
+                self.ticket.set(None);
+            }
+            _ => {}
+        }
+        (self.handler)(message);
+    }
+
+    /// Tries again with growing pauses while there is a seat to go back
+    /// to. Before that there is nothing to save, and the player is told.
+    fn dropped(this: Arc<Self>) {
+        if this.status.get() == Status::Closed {
+            return;
+        }
+        if this.ticket().is_none() {
+            this.receive(ServerMessage::Rejected {
+                error: OnlineError::Unreachable(this.url.clone()),
+            });
+            return;
+        }
+
+        this.status.set(Status::Reconnecting);
+        let attempts = this.attempts.get();
+        this.attempts.set(attempts + 1);
+        let delay = RETRY_DELAY.saturating_mul(1 << attempts.min(5)).min(MAX_RETRY_DELAY);
+
+        let weak: Weak<Self> = Arc::downgrade(&this);
+        spawn_local(async move {
+            TimeoutFuture::new(delay).await;
+            if let Some(this) = weak.upgrade() {
+                if this.status.get() == Status::Reconnecting {
+                    Connection::connect(&this);
+                }
+            }
+        });
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        let id = self.id;
+        // Not `with`: the thread local may be gone when the page unloads.
+        let _ = SOCKETS.try_with(|sockets| sockets.borrow_mut().remove(&id));
+    }
+}
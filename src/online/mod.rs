@@ -0,0 +1,9 @@
+//! Games played over the network, with the board kept on a server.
+//!
+//! `protocol` is what travels over the WebSocket, `room` the rules the
+//! server enforces, and `client` the browser end. The server binary in
+//! `server/` is a thin loop around `room::Lobby`.
+
+pub mod client;
+pub mod protocol;
+pub mod room;
@@ -0,0 +1,73 @@
+//! Messages between the browser and the server, sent as JSON text frames.
+
+use serde::{Deserialize, Serialize};
+
+use super::room::RoomError;
+use crate::engine::{Game, GameEvent};
+use crate::profiles::Profile;
+use crate::Config;
+
+pub const DEFAULT_PORT: u16 = 9001;
+
+/// A seat in a room and the secret that lets its player sit there again
+/// after the connection drops.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ticket {
+    pub code: String,
+    pub seat: usize,
+    pub token: String,
+}
+
+/// Who sits in a taken seat, and whether they are still connected.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SeatView {
+    pub profile: Profile,
+    pub connected: bool,
+}
+
+/// What went wrong, sent as data rather than text so that every player
+/// reads it in their own language; see `Locale::online_error`. The last
+/// two never come from the server but from the browser failing to reach
+/// it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OnlineError {
+    Room(RoomError),
+    Unreadable,
+    AlreadySeated,
+    BadAddress(String),
+    Unreachable(String),
+}
+
+impl From<RoomError> for OnlineError {
+    fn from(error: RoomError) -> Self {
+        OnlineError::Room(error)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ClientMessage {
+    Create { config: Config, profile: Profile },
+    Join { code: String, profile: Profile },
+    Rejoin(Ticket),
+    Flip { position: usize },
+    Leave,
+}
+
+/// Games are always sent `Game::redacted`, so cards nobody is looking at
+/// can't be read from the wire.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ServerMessage {
+    Joined(Ticket),
+    Room {
+        config: Config,
+        seats: Vec<Option<SeatView>>,
+        game: Game,
+    },
+    Update { events: Vec<GameEvent>, game: Game },
+    /// Something the player tried was not allowed; the seat is kept.
+    Error { error: OnlineError },
+    /// The room could not be entered. The server hangs up after this.
+    Rejected { error: OnlineError },
+}
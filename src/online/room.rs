@@ -0,0 +1,371 @@
+//! Rooms on the server: who sits where and which flips they may make.
+//!
+//! A room is made from the host's `Config` and waits until every seat is
+//! taken before the first flip. The `Game` in it is the only real one; the
+//! players' browsers just show what it sends them.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::protocol::{SeatView, ServerMessage, Ticket};
+use crate::engine::{self, Game, GameEvent, Phase, SizeError};
+use crate::profiles::{Profile, SEATS};
+use crate::Config;
+
+/// Letters that can't be mistaken for digits when read out.
+const CODE_LETTERS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+const CODE_LENGTH: usize = 4;
+
+/// Sent to the player as is, see `protocol::OnlineError`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoomError {
+    Size(SizeError),
+    Players(usize),
+    UnknownRoom(String),
+    Full,
+    BadTicket,
+    NotSeated,
+    Waiting,
+    NotYourTurn,
+}
+
+#[derive(Clone, Debug)]
+struct Seat {
+    profile: Profile,
+    token: String,
+    connected: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct Room {
+    config: Config,
+    game: Game,
+    seats: Vec<Option<Seat>>,
+}
+
+#[derive(Debug, Default)]
+pub struct Lobby {
+    rooms: HashMap<String, Room>,
+}
+
+/// Join codes are typed by hand, so case and outer spaces don't matter.
+pub fn normalize_code(code: &str) -> String {
+    code.trim().to_uppercase()
+}
+
+fn random_code<R: Rng>(rng: &mut R) -> String {
+    (0..CODE_LENGTH)
+        .map(|_| CODE_LETTERS[rng.gen_range(0..CODE_LETTERS.len())] as char)
+        .collect()
+}
+
+fn random_token<R: Rng>(rng: &mut R) -> String {
+    format!("{:016x}{:016x}", rng.gen::<u64>(), rng.gen::<u64>())
+}
+
+impl Room {
+    /// Hints, computer seats, the shot clock and the daily board are for
+    /// playing at home, so they are turned off here.
+    pub fn new(config: Config) -> Result<Self, RoomError> {
+        engine::check_size(config.rows, config.columns, config.set_size).map_err(RoomError::Size)?;
+        if !(2..=SEATS).contains(&config.players) {
+            return Err(RoomError::Players(config.players));
+        }
+
+        let config = Config {
+            daily: None,
+            hints: 0,
+            computers: [None; SEATS],
+            shot_clock: None,
+            ..config
+        };
+        Ok(Room {
+            game: Game::new(config.setup()),
+            seats: vec![None; config.players],
+            config,
+        })
+    }
+
+    /// Seats `profile` in the first free seat.
+    pub fn join(&mut self, profile: Profile, token: String) -> Result<usize, RoomError> {
+        let seat = self.seats.iter().position(Option::is_none).ok_or(RoomError::Full)?;
+        self.seats[seat] = Some(Seat {
+            profile,
+            token,
+            connected: true,
+        });
+        Ok(seat)
+    }
+
+    pub fn rejoin(&mut self, seat: usize, token: &str) -> Result<(), RoomError> {
+        let forfeited = self.game.players().get(seat).is_some_and(|p| p.forfeited);
+        match self.seats.get_mut(seat) {
+            Some(Some(taken)) if taken.token == token && !forfeited => {
+                taken.connected = true;
+                Ok(())
+            }
+            _ => Err(RoomError::BadTicket),
+        }
+    }
+
+    /// The player's connection dropped; the seat waits for a `rejoin`.
+    pub fn disconnect(&mut self, seat: usize) {
+        if let Some(Some(taken)) = self.seats.get_mut(seat) {
+            taken.connected = false;
+        }
+    }
+
+    /// The player left on purpose. Before the game starts the seat is
+    /// freed for someone else; after, it is forfeited and the others play
+    /// on without it, see `Game::forfeit`.
+    pub fn leave(&mut self, seat: usize) -> Vec<GameEvent> {
+        if self.is_full() {
+            self.disconnect(seat);
+            self.game.forfeit(seat)
+        } else {
+            if let Some(taken) = self.seats.get_mut(seat) {
+                *taken = None;
+            }
+            vec![]
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.seats.iter().all(Option::is_some)
+    }
+
+    /// Nobody is connected any more.
+    pub fn is_deserted(&self) -> bool {
+        !self.seats.iter().flatten().any(|s| s.connected)
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.game.phase()
+    }
+
+    /// `Game::flip_at` for the player in `seat`, once everyone is seated.
+    pub fn flip(&mut self, seat: usize, position: usize, at: f64) -> Result<Vec<GameEvent>, RoomError> {
+        if !self.is_full() {
+            return Err(RoomError::Waiting);
+        }
+        if self.game.turn() != seat {
+            return Err(RoomError::NotYourTurn);
+        }
+        Ok(self.game.flip_at(position, at))
+    }
+
+    pub fn resolve(&mut self, at: f64) -> Vec<GameEvent> {
+        self.game.resolve_at(at)
+    }
+
+    /// The whole room, for players joining or coming back. Like the
+    /// game, the config only gives the seed away once it is over.
+    pub fn message(&self) -> ServerMessage {
+        let game = self.game.redacted();
+        ServerMessage::Room {
+            config: Config {
+                seed: game.setup().seed,
+                ..self.config.clone()
+            },
+            seats: self
+                .seats
+                .iter()
+                .map(|seat| {
+                    seat.as_ref().map(|s| SeatView {
+                        profile: s.profile.clone(),
+                        connected: s.connected,
+                    })
+                })
+                .collect(),
+            game,
+        }
+    }
+
+    pub fn update(&self, events: Vec<GameEvent>) -> ServerMessage {
+        ServerMessage::Update {
+            events,
+            game: self.game.redacted(),
+        }
+    }
+}
+
+impl Lobby {
+    /// Opens a room for `config` with its host in the first seat. The
+    /// board is dealt from a seed of the server's own, so nobody, the
+    /// host included, knows it in advance.
+    pub fn create<R: Rng>(&mut self, config: Config, profile: Profile, rng: &mut R) -> Result<Ticket, RoomError> {
+        let mut room = Room::new(Config { seed: rng.gen(), ..config })?;
+        let code = loop {
+            let code = random_code(rng);
+            if !self.rooms.contains_key(&code) {
+                break code;
+            }
+        };
+        let token = random_token(rng);
+        let seat = room.join(profile, token.clone())?;
+        self.rooms.insert(code.clone(), room);
+        Ok(Ticket { code, seat, token })
+    }
+
+    pub fn join<R: Rng>(&mut self, code: &str, profile: Profile, rng: &mut R) -> Result<Ticket, RoomError> {
+        let code = normalize_code(code);
+        let token = random_token(rng);
+        let seat = self.room(&code)?.join(profile, token.clone())?;
+        Ok(Ticket { code, seat, token })
+    }
+
+    pub fn rejoin(&mut self, ticket: &Ticket) -> Result<(), RoomError> {
+        self.room(&ticket.code)?.rejoin(ticket.seat, &ticket.token)
+    }
+
+    pub fn room(&mut self, code: &str) -> Result<&mut Room, RoomError> {
+        self.rooms
+            .get_mut(code)
+            .ok_or_else(|| RoomError::UnknownRoom(code.to_string()))
+    }
+
+    /// Drops the room at `code` if nobody is left in it. Returns whether
+    /// it was dropped.
+    pub fn close_if_deserted(&mut self, code: &str) -> bool {
+        if self.rooms.get(code).is_some_and(Room::is_deserted) {
+            self.rooms.remove(code);
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameTheme;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn config(players: usize) -> Config {
+        Config {
+            theme: GameTheme::Numbers,
+            players,
+            rows: 4,
+            columns: 4,
+            set_size: 2,
+            seed: 5,
+            daily: None,
+            hints: 3,
+            computers: [None; SEATS],
+            scoring: Default::default(),
+            turns: Default::default(),
+            shot_clock: Some(10),
+        }
+    }
+
+    fn seated(players: usize) -> (Lobby, Vec<Ticket>) {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut lobby = Lobby::default();
+        let host = lobby.create(config(players), Profile::new(0), &mut rng).unwrap();
+        let mut tickets = vec![host.clone()];
+        for seat in 1..players {
+            let code = host.code.to_lowercase();
+            tickets.push(lobby.join(&code, Profile::new(seat), &mut rng).unwrap());
+        }
+        (lobby, tickets)
+    }
+
+    #[test]
+    fn rooms_need_company_and_a_valid_board() {
+        assert_eq!(Room::new(config(1)).unwrap_err(), RoomError::Players(1));
+        let odd = Config { rows: 3, columns: 3, ..config(2) };
+        assert!(matches!(Room::new(odd), Err(RoomError::Size(_))));
+
+        let room = Room::new(config(2)).unwrap();
+        assert_eq!(room.config.hints, 0);
+        assert_eq!(room.config.shot_clock, None);
+    }
+
+    #[test]
+    fn players_take_seats_until_the_room_is_full() {
+        let (mut lobby, tickets) = seated(2);
+        let code = tickets[0].code.clone();
+        assert_eq!(tickets.iter().map(|t| t.seat).collect::<Vec<_>>(), vec![0, 1]);
+
+        let mut rng = StdRng::seed_from_u64(2);
+        assert_eq!(lobby.join(&code, Profile::new(2), &mut rng).unwrap_err(), RoomError::Full);
+        assert_eq!(
+            lobby.join("ZZZZ", Profile::new(2), &mut rng).unwrap_err(),
+            RoomError::UnknownRoom("ZZZZ".into())
+        );
+    }
+
+    #[test]
+    fn only_the_player_in_turn_flips_once_everyone_is_in() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut lobby = Lobby::default();
+        let host = lobby.create(config(2), Profile::new(0), &mut rng).unwrap();
+        let room = lobby.room(&host.code).unwrap();
+        assert_eq!(room.flip(0, 0, 0.0).unwrap_err(), RoomError::Waiting);
+
+        room.join(Profile::new(1), "guest".into()).unwrap();
+        assert_eq!(room.flip(1, 0, 0.0).unwrap_err(), RoomError::NotYourTurn);
+        assert_eq!(room.flip(0, 0, 0.0).unwrap(), vec![GameEvent::Flipped { position: 0 }]);
+    }
+
+    #[test]
+    fn a_dropped_player_comes_back_with_their_ticket() {
+        let (mut lobby, tickets) = seated(2);
+        let code = tickets[0].code.clone();
+
+        lobby.room(&code).unwrap().disconnect(1);
+        let stolen = Ticket {
+            token: tickets[0].token.clone(),
+            ..tickets[1].clone()
+        };
+        assert_eq!(lobby.rejoin(&stolen).unwrap_err(), RoomError::BadTicket);
+        lobby.rejoin(&tickets[1]).unwrap();
+
+        lobby.room(&code).unwrap().disconnect(0);
+        lobby.room(&code).unwrap().disconnect(1);
+        assert!(lobby.close_if_deserted(&code));
+        assert!(lobby.rejoin(&tickets[1]).is_err());
+    }
+
+    #[test]
+    fn play_goes_on_without_a_player_who_left() {
+        let (mut lobby, tickets) = seated(3);
+        let room = lobby.room(&tickets[0].code).unwrap();
+        room.flip(0, 0, 0.0).unwrap();
+
+        let events = room.leave(0);
+        assert!(events.contains(&GameEvent::TurnChanged { player: 1 }));
+        assert!(room.flip(1, 0, 0.0).unwrap().contains(&GameEvent::Flipped { position: 0 }));
+
+        room.leave(2);
+        let pair = room.game.cards().iter().position(|c| c.value != room.game.cards()[0].value).unwrap();
+        room.flip(1, pair, 0.0).unwrap();
+        room.resolve(0.0);
+        assert_eq!(room.game.turn(), 1);
+        assert_eq!(room.rejoin(0, &tickets[0].token).unwrap_err(), RoomError::BadTicket);
+    }
+
+    #[test]
+    fn face_down_cards_never_leave_the_server() {
+        let (mut lobby, tickets) = seated(2);
+        let room = lobby.room(&tickets[0].code).unwrap();
+        let seed = room.config.seed;
+        assert_ne!(seed, config(2).seed);
+        let events = room.flip(0, 3, 0.0).unwrap();
+
+        let message = room.message();
+        match &message {
+            ServerMessage::Room { game, .. } => {
+                let mut hidden = game.cards().iter().enumerate().filter(|&(p, _)| p != 3);
+                assert!(hidden.all(|(_, c)| c.value == 0));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        for message in [message, room.update(events)] {
+            let text = serde_json::to_string(&message).unwrap();
+            assert!(!text.contains(&seed.to_string()), "{}", text);
+        }
+    }
+}
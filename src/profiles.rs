@@ -0,0 +1,130 @@
+//! Names and colors for the four seats, remembered in `localStorage`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::Locale;
+
+pub const STORAGE_KEY: &str = "players";
+
+/// Most players a game can have, and so the number of stored profiles.
+pub const SEATS: usize = 4;
+
+pub const MAX_NAME: usize = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Color {
+    Orange,
+    Blue,
+    Green,
+    Purple,
+    Pink,
+    Teal,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub color: Color,
+}
+
+impl Color {
+    pub const ALL: [Color; 6] = [
+        Color::Orange,
+        Color::Blue,
+        Color::Green,
+        Color::Purple,
+        Color::Pink,
+        Color::Teal,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Color::Orange => "orange",
+            Color::Blue => "blue",
+            Color::Green => "green",
+            Color::Purple => "purple",
+            Color::Pink => "pink",
+            Color::Teal => "teal",
+        }
+    }
+
+    pub fn css(&self) -> &'static str {
+        match self {
+            Color::Orange => "#fda214",
+            Color::Blue => "#3f7bd9",
+            Color::Green => "#3fa66b",
+            Color::Purple => "#8a5cd1",
+            Color::Pink => "#e0578f",
+            Color::Teal => "#2aa7a7",
+        }
+    }
+}
+
+impl Profile {
+    pub fn new(seat: usize) -> Self {
+        Profile {
+            name: String::new(),
+            color: Color::ALL[seat % Color::ALL.len()],
+        }
+    }
+
+    pub fn display_name(&self, locale: Locale, seat: usize) -> String {
+        display_name(locale, &self.name, seat)
+    }
+}
+
+/// What a seat is called on screen: the typed name without outer spaces, or
+/// the seat's default name in `locale` when none was given.
+pub fn display_name(locale: Locale, name: &str, seat: usize) -> String {
+    let name: String = name.trim().chars().take(MAX_NAME).collect();
+    if name.is_empty() {
+        locale.t_with("player.default", &[("number", &(seat + 1))])
+    } else {
+        name
+    }
+}
+
+/// The first letter of a name, shown on its avatar.
+pub fn initial(name: &str) -> String {
+    name.chars().next().into_iter().flat_map(char::to_uppercase).collect()
+}
+
+/// One profile per seat, filling in any missing from an older store.
+pub fn complete(mut profiles: Vec<Profile>) -> Vec<Profile> {
+    profiles.truncate(SEATS);
+    for seat in profiles.len()..SEATS {
+        profiles.push(Profile::new(seat));
+    }
+    profiles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blank_names_fall_back_to_the_seat() {
+        let mut profile = Profile::new(2);
+        assert_eq!(profile.display_name(Locale::En, 2), "Player3");
+
+        profile.name = "   ".into();
+        assert_eq!(profile.display_name(Locale::Es, 2), "Jugador3");
+
+        profile.name = "  ada lovelace, countess  ".into();
+        assert_eq!(profile.display_name(Locale::Es, 2), "ada lovelace, co");
+        assert_eq!(initial(&profile.display_name(Locale::En, 2)), "A");
+    }
+
+    #[test]
+    fn every_seat_gets_a_profile() {
+        let stored = vec![Profile {
+            name: "Ada".into(),
+            color: Color::Teal,
+        }];
+        let profiles = complete(stored);
+
+        assert_eq!(profiles.len(), SEATS);
+        assert_eq!(profiles[0].color, Color::Teal);
+        assert_eq!(profiles[3], Profile::new(3));
+    }
+}
@@ -0,0 +1,111 @@
+//! Personal bests per board setup, kept in `localStorage`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::{Config, GameTheme};
+
+pub const STORAGE_KEY: &str = "records";
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Best {
+    pub time: f64,
+    pub moves: usize,
+}
+
+/// Which parts of a `Best` the last finished game beat.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Improvement {
+    pub time: bool,
+    pub moves: bool,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Records {
+    bests: BTreeMap<String, Best>,
+}
+
+impl Improvement {
+    pub fn any(&self) -> bool {
+        self.time || self.moves
+    }
+}
+
+impl Records {
+    /// Records are kept apart for every grid size, set size, theme and
+    /// player count. Pairs keep the shorter key they had before sets.
+    pub fn key(cfg: &Config) -> String {
+        let theme = match cfg.theme {
+            GameTheme::Numbers => "numbers",
+            GameTheme::Icons => "icons",
+        };
+        let mut key = format!("{}x{}-{}-{}p", cfg.rows, cfg.columns, theme, cfg.players);
+        if cfg.set_size != 2 {
+            key.push_str(&format!("-match{}", cfg.set_size));
+        }
+        key
+    }
+
+    pub fn get(&self, cfg: &Config) -> Option<Best> {
+        self.bests.get(&Records::key(cfg)).copied()
+    }
+
+    pub fn submit(&mut self, cfg: &Config, time: f64, moves: usize) -> Improvement {
+        let key = Records::key(cfg);
+        match self.bests.get_mut(&key) {
+            Some(best) => {
+                let improvement = Improvement {
+                    time: time < best.time,
+                    moves: moves < best.moves,
+                };
+                best.time = best.time.min(time);
+                best.moves = best.moves.min(moves);
+                improvement
+            }
+            None => {
+                self.bests.insert(key, Best { time, moves });
+                Improvement { time: true, moves: true }
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.bests.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(rows: usize, players: usize) -> Config {
+        Config {
+            rows,
+            columns: 4,
+            players,
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn bests_are_kept_per_setup() {
+        let mut records = Records::default();
+        records.submit(&config(4, 1), 60_000.0, 20);
+        records.submit(&config(6, 1), 90_000.0, 40);
+
+        assert_eq!(records.get(&config(4, 1)), Some(Best { time: 60_000.0, moves: 20 }));
+        assert_eq!(records.get(&config(4, 2)), None);
+    }
+
+    #[test]
+    fn time_and_moves_improve_independently() {
+        let mut records = Records::default();
+        let cfg = config(4, 1);
+        records.submit(&cfg, 60_000.0, 20);
+
+        let improvement = records.submit(&cfg, 50_000.0, 25);
+
+        assert_eq!(improvement, Improvement { time: true, moves: false });
+        assert_eq!(records.get(&cfg), Some(Best { time: 50_000.0, moves: 20 }));
+    }
+}
@@ -0,0 +1,151 @@
+//! The JSON file a finished game is shared as.
+//!
+//! Only what is needed to deal the board again is stored: the config (which
+//! carries the seed), the player names and the move log. `version` is bumped
+//! whenever the layout changes so older builds can refuse newer files.
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{self, replay::Entry, SizeError};
+use crate::profiles;
+use crate::Config;
+
+/// The newest format this build writes and reads.
+pub const VERSION: u32 = 1;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayFile {
+    pub version: u32,
+    pub config: Config,
+    pub players: Vec<String>,
+    pub moves: Vec<Entry>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImportError {
+    /// The browser couldn't hand over the file's text.
+    Unreadable,
+    Malformed(String),
+    NewerVersion(u32),
+    Invalid(Invalid),
+}
+
+/// Why a well-formed file still describes a game that can't be shown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Invalid {
+    Size(SizeError),
+    Players(usize),
+    Names { names: usize, players: usize },
+}
+
+/// Read before the rest so a newer file is reported as such, not as broken.
+#[derive(Deserialize)]
+struct Header {
+    version: u32,
+}
+
+impl ReplayFile {
+    pub fn new(config: Config, players: Vec<String>, moves: Vec<Entry>) -> Self {
+        ReplayFile {
+            version: VERSION,
+            config,
+            players,
+            moves,
+        }
+    }
+
+    pub fn file_name(&self) -> String {
+        format!("memory-replay-{}.json", self.config.seed)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, ImportError> {
+        let header: Header = serde_json::from_str(text).map_err(|e| ImportError::Malformed(e.to_string()))?;
+        if header.version > VERSION {
+            return Err(ImportError::NewerVersion(header.version));
+        }
+
+        let file: ReplayFile = serde_json::from_str(text).map_err(|e| ImportError::Malformed(e.to_string()))?;
+        let cfg = &file.config;
+        engine::check_size(cfg.rows, cfg.columns, cfg.set_size)
+            .map_err(|e| ImportError::Invalid(Invalid::Size(e)))?;
+        if !(1..=profiles::SEATS).contains(&cfg.players) {
+            return Err(ImportError::Invalid(Invalid::Players(cfg.players)));
+        }
+        if file.players.len() != cfg.players {
+            return Err(ImportError::Invalid(Invalid::Names {
+                names: file.players.len(),
+                players: cfg.players,
+            }));
+        }
+        Ok(file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::GameEvent;
+    use crate::GameTheme;
+
+    fn file() -> ReplayFile {
+        let config = Config {
+            theme: GameTheme::Icons,
+            players: 2,
+            rows: 2,
+            columns: 3,
+            set_size: 2,
+            seed: 42,
+            daily: None,
+            hints: 3,
+            computers: [None; profiles::SEATS],
+            scoring: Default::default(),
+            turns: Default::default(),
+            shot_clock: None,
+        };
+        let moves = vec![Entry {
+            at: 1200.0,
+            event: GameEvent::Flipped { position: 4 },
+        }];
+        ReplayFile::new(config, vec!["Ada".into(), "Grace".into()], moves)
+    }
+
+    #[test]
+    fn files_read_back_what_was_written() {
+        let json = file().to_json();
+        let read = ReplayFile::from_json(&json).unwrap();
+
+        assert_eq!(read.config.seed, 42);
+        assert_eq!(read.players, vec!["Ada", "Grace"]);
+        assert_eq!(read.moves, file().moves);
+        assert_eq!(read.to_json(), json);
+    }
+
+    #[test]
+    fn newer_versions_are_refused_before_parsing() {
+        let json = format!(r#"{{"version": {}, "something": "new"}}"#, VERSION + 1);
+
+        assert_eq!(ReplayFile::from_json(&json).unwrap_err(), ImportError::NewerVersion(VERSION + 1));
+    }
+
+    #[test]
+    fn broken_and_impossible_files_are_rejected() {
+        assert!(matches!(ReplayFile::from_json("not json"), Err(ImportError::Malformed(_))));
+        assert!(matches!(ReplayFile::from_json(r#"{"version": 1}"#), Err(ImportError::Malformed(_))));
+
+        let mut odd = file();
+        odd.config.rows = 3;
+        odd.config.columns = 3;
+        assert!(matches!(ReplayFile::from_json(&odd.to_json()), Err(ImportError::Invalid(Invalid::Size(_)))));
+
+        let mut unnamed = file();
+        unnamed.players.pop();
+        assert_eq!(
+            ReplayFile::from_json(&unnamed.to_json()).unwrap_err(),
+            ImportError::Invalid(Invalid::Names { names: 1, players: 2 })
+        );
+    }
+}
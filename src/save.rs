@@ -0,0 +1,25 @@
+//! The game in progress, autosaved to `localStorage` after every move.
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::replay::Entry;
+use crate::engine::Game;
+use crate::Config;
+
+pub const STORAGE_KEY: &str = "saved_game";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub config: Config,
+    pub game: Game,
+    pub elapsed: f64,
+    /// Saves from before the move log was kept resume with an empty one.
+    #[serde(default)]
+    pub log: Vec<Entry>,
+    /// Moves were undone, so the game doesn't count for records.
+    #[serde(default)]
+    pub practice: bool,
+    /// The game is the scored attempt of its daily challenge.
+    #[serde(default)]
+    pub daily_attempt: bool,
+}
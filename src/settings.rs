@@ -0,0 +1,40 @@
+//! Preferences of whoever uses this device, kept in `localStorage`.
+//!
+//! Unlike `Config` they don't change the game itself, so they are not part
+//! of saves, replays or online rooms.
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::Locale;
+use crate::timing::Speed;
+
+pub const STORAGE_KEY: &str = "settings";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Settings {
+    #[serde(default)]
+    pub speed: Speed,
+    /// Sound effects volume, from 0 to 1.
+    #[serde(default = "default_volume")]
+    pub volume: f64,
+    #[serde(default)]
+    pub muted: bool,
+    /// `None` follows the browser's languages.
+    #[serde(default)]
+    pub locale: Option<Locale>,
+}
+
+fn default_volume() -> f64 {
+    0.6
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            speed: Speed::default(),
+            volume: default_volume(),
+            muted: false,
+            locale: None,
+        }
+    }
+}
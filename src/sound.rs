@@ -0,0 +1,159 @@
+//! Sound effects, made on the fly with the Web Audio API.
+//!
+//! Each `Sound` is a few short `Tone`s; there are no audio files to load.
+//! Browsers keep audio suspended until the player clicks or presses a
+//! key, and many sounds come without one: the computer's moves, the other
+//! players' online, a clock running out. So the `AudioContext` is made or
+//! resumed by `unlock` on every click and key press, and sounds played
+//! before the first are lost. Like the sockets in `online::client` it is a
+//! JavaScript object, so it lives in a thread local.
+
+use std::cell::RefCell;
+use wasm_bindgen::JsValue;
+use web_sys::{AudioContext, AudioContextState, OscillatorType};
+
+/// Gain of a tone at full volume. Square waves sound much louder, so they
+/// get a third of it.
+const PEAK: f32 = 0.3;
+/// Seconds a tone takes to fade in, so it doesn't click.
+const ATTACK: f64 = 0.01;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sound {
+    Flip,
+    Match,
+    Mismatch,
+    TurnChange,
+    Victory,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Wave {
+    Sine,
+    Triangle,
+    Square,
+}
+
+/// One note: `start` and `length` in seconds from when the sound plays.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tone {
+    pub wave: Wave,
+    pub frequency: f32,
+    pub start: f64,
+    pub length: f64,
+}
+
+const fn tone(wave: Wave, frequency: f32, start: f64, length: f64) -> Tone {
+    Tone {
+        wave,
+        frequency,
+        start,
+        length,
+    }
+}
+
+const FLIP: &[Tone] = &[tone(Wave::Triangle, 660.0, 0.0, 0.06)];
+const MATCH: &[Tone] = &[tone(Wave::Sine, 659.25, 0.0, 0.1), tone(Wave::Sine, 987.77, 0.08, 0.18)];
+const MISMATCH: &[Tone] = &[tone(Wave::Square, 220.0, 0.0, 0.12), tone(Wave::Square, 185.0, 0.1, 0.18)];
+const TURN_CHANGE: &[Tone] = &[tone(Wave::Sine, 523.25, 0.0, 0.08)];
+/// C major arpeggio, ending on the high C.
+const VICTORY: &[Tone] = &[
+    tone(Wave::Triangle, 523.25, 0.0, 0.12),
+    tone(Wave::Triangle, 659.25, 0.12, 0.12),
+    tone(Wave::Triangle, 783.99, 0.24, 0.12),
+    tone(Wave::Triangle, 1046.5, 0.36, 0.4),
+];
+
+thread_local! {
+    static CONTEXT: RefCell<Option<AudioContext>> = const { RefCell::new(None) };
+}
+
+impl Sound {
+    pub fn tones(&self) -> &'static [Tone] {
+        match self {
+            Sound::Flip => FLIP,
+            Sound::Match => MATCH,
+            Sound::Mismatch => MISMATCH,
+            Sound::TurnChange => TURN_CHANGE,
+            Sound::Victory => VICTORY,
+        }
+    }
+}
+
+/// Lets sounds play from now on. Only works while handling a click or
+/// key press, and before the handlers that may play one.
+pub fn unlock() {
+    let _ = CONTEXT.try_with(|context| {
+        let mut context = context.borrow_mut();
+        if context.is_none() {
+            *context = AudioContext::new().ok();
+        }
+        if let Some(context) = context.as_ref() {
+            if context.state() == AudioContextState::Suspended {
+                let _ = context.resume();
+            }
+        }
+    });
+}
+
+/// Plays `sound` at `volume`, from 0 to 1. Before `unlock`, or in a
+/// browser without Web Audio, it stays silent.
+pub fn play(sound: Sound, volume: f64) {
+    let _ = CONTEXT.try_with(|context| {
+        // Right after `unlock` the context may still be resuming; sounds
+        // scheduled meanwhile play as soon as it is done.
+        if let Some(context) = context.borrow().as_ref() {
+            if context.state() != AudioContextState::Closed {
+                let _ = schedule(context, sound, volume as f32);
+            }
+        }
+    });
+}
+
+fn schedule(context: &AudioContext, sound: Sound, volume: f32) -> Result<(), JsValue> {
+    let now = context.current_time();
+    for tone in sound.tones() {
+        let (kind, peak) = match tone.wave {
+            Wave::Sine => (OscillatorType::Sine, PEAK),
+            Wave::Triangle => (OscillatorType::Triangle, PEAK),
+            Wave::Square => (OscillatorType::Square, PEAK / 3.0),
+        };
+        let start = now + tone.start;
+        let end = start + tone.length;
+
+        let oscillator = context.create_oscillator()?;
+        oscillator.set_type(kind);
+        oscillator.frequency().set_value(tone.frequency);
+
+        let gain = context.create_gain()?;
+        let level = gain.gain();
+        level.set_value_at_time(0.0, start)?;
+        level.linear_ramp_to_value_at_time(peak * volume, start + ATTACK)?;
+        // Exponential ramps can't reach 0; this is far below hearing.
+        level.exponential_ramp_to_value_at_time(0.0001, end)?;
+
+        oscillator.connect_with_audio_node(&gain)?;
+        gain.connect_with_audio_node(&context.destination())?;
+        oscillator.start_with_when(start)?;
+        oscillator.stop_with_when(end)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sounds_are_short_and_audible() {
+        for sound in [Sound::Flip, Sound::Match, Sound::Mismatch, Sound::TurnChange, Sound::Victory] {
+            let tones = sound.tones();
+            assert!(!tones.is_empty());
+            for tone in tones {
+                assert!((100.0..4000.0).contains(&tone.frequency));
+                assert!(tone.length > ATTACK);
+                assert!(tone.start + tone.length < 1.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//! Thin JSON wrappers around the browser's `localStorage`.
+//!
+//! Every failure (no window, storage disabled, bad JSON) is treated as "no
+//! value" so a broken store never stops the game from running.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use web_sys::Storage;
+
+const PREFIX: &str = "memory_game.";
+
+fn local_storage() -> Option<Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let raw = local_storage()?.get_item(&format!("{}{}", PREFIX, key)).ok()??;
+    serde_json::from_str(&raw).ok()
+}
+
+pub fn save<T: Serialize>(key: &str, value: &T) {
+    if let (Some(storage), Ok(raw)) = (local_storage(), serde_json::to_string(value)) {
+        let _ = storage.set_item(&format!("{}{}", PREFIX, key), &raw);
+    }
+}
+
+pub fn remove(key: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.remove_item(&format!("{}{}", PREFIX, key));
+    }
+}
@@ -0,0 +1,148 @@
+//! How long the board takes to show what happened.
+//!
+//! Every delay between a flip and the board settling, and the length of
+//! the card and score animations, comes from one `Timing`. It follows the
+//! chosen `Speed`, and drops the motion when the device asks for reduced
+//! motion while keeping the cards up long enough to be read.
+
+use serde::{Deserialize, Serialize};
+
+/// A missed set stays up at least this long, even at `Speed::Instant`, or
+/// there would be nothing to remember. In milliseconds.
+const MIN_MISS_HOLD: u32 = 400;
+/// Computer flips are never quicker than this, so they can be followed.
+const MIN_BOT_DELAY: u32 = 250;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Speed {
+    Slow,
+    #[default]
+    Normal,
+    Fast,
+    /// No animations and no waiting beyond what is needed to play.
+    Instant,
+}
+
+/// Delays in milliseconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Timing {
+    /// Whether cards and scores move at all.
+    pub animate: bool,
+    /// Length of a full animate.css animation; flips run at half of it.
+    pub animation: u32,
+    /// From turning the last card of a set to showing it matched.
+    pub match_reveal: u32,
+    /// From showing a match to settling the set.
+    pub match_hold: u32,
+    /// From turning the card that breaks a set to showing the miss.
+    pub miss_reveal: u32,
+    /// From showing a miss to turning the cards back.
+    pub miss_hold: u32,
+    /// How long a new score bounces.
+    pub points: u32,
+    /// From the last set to the results.
+    pub results: u32,
+    /// Before each computer flip.
+    pub bot: u32,
+}
+
+impl Speed {
+    pub const ALL: [Speed; 4] = [Speed::Slow, Speed::Normal, Speed::Fast, Speed::Instant];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Speed::Slow => "slow",
+            Speed::Normal => "normal",
+            Speed::Fast => "fast",
+            Speed::Instant => "instant",
+        }
+    }
+
+    fn factor(&self) -> f64 {
+        match self {
+            Speed::Slow => 1.5,
+            Speed::Normal => 1.0,
+            Speed::Fast => 0.5,
+            Speed::Instant => 0.0,
+        }
+    }
+}
+
+impl Timing {
+    pub fn new(speed: Speed, reduced_motion: bool) -> Self {
+        let scale = |ms: u32| (ms as f64 * speed.factor()) as u32;
+        let animate = speed != Speed::Instant && !reduced_motion;
+        // Without motion there is nothing to wait for before the colors change.
+        let motion = |ms: u32| if animate { scale(ms) } else { 0 };
+
+        Timing {
+            animate,
+            animation: motion(1000),
+            match_reveal: motion(300),
+            match_hold: scale(450),
+            miss_reveal: motion(100),
+            miss_hold: scale(900).max(MIN_MISS_HOLD),
+            points: motion(300),
+            results: scale(500),
+            bot: scale(800).max(MIN_BOT_DELAY),
+        }
+    }
+
+    /// From turning the last card of a set to it being settled, whether it
+    /// matched or not.
+    pub fn settle(&self) -> u32 {
+        (self.match_reveal + self.match_hold).max(self.miss_reveal + self.miss_hold)
+    }
+}
+
+/// The longest any speed keeps a turned set up. Online games wait this out
+/// before settling, so every player sees the set whatever their speed.
+pub fn slowest_settle() -> u32 {
+    Speed::ALL.iter().map(|&speed| Timing::new(speed, false).settle()).max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_scale_every_delay() {
+        let normal = Timing::new(Speed::Normal, false);
+        assert_eq!((normal.match_reveal, normal.match_hold), (300, 450));
+        assert_eq!((normal.miss_reveal, normal.miss_hold), (100, 900));
+
+        let slow = Timing::new(Speed::Slow, false);
+        assert_eq!(slow.miss_hold, 1350);
+        let fast = Timing::new(Speed::Fast, false);
+        assert_eq!(fast.animation, 500);
+        assert_eq!(fast.bot, 400);
+    }
+
+    #[test]
+    fn instant_stops_moving_but_still_shows_misses() {
+        let instant = Timing::new(Speed::Instant, false);
+        assert!(!instant.animate);
+        assert_eq!((instant.match_reveal, instant.match_hold), (0, 0));
+        assert_eq!(instant.miss_hold, MIN_MISS_HOLD);
+        assert_eq!(instant.bot, MIN_BOT_DELAY);
+    }
+
+    #[test]
+    fn reduced_motion_keeps_the_reading_time() {
+        let reduced = Timing::new(Speed::Normal, true);
+        assert!(!reduced.animate);
+        assert_eq!(reduced.miss_reveal, 0);
+        assert_eq!(reduced.miss_hold, 900);
+        assert_eq!(reduced.bot, 800);
+    }
+
+    #[test]
+    fn slowest_settle_outlasts_every_preset() {
+        assert_eq!(slowest_settle(), 1500);
+        for &speed in &Speed::ALL {
+            for &reduced in &[false, true] {
+                assert!(Timing::new(speed, reduced).settle() <= slowest_settle());
+            }
+        }
+    }
+}